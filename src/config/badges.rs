@@ -4,99 +4,372 @@ use std::collections::BTreeMap;
 
 use percent_encoding as pe;
 
+use super::forge::Forge;
+use super::metadata::{BadgeStyle, CustomBadge};
+use super::provider::{BadgeContext, BadgeRegistry};
+use crate::suggest;
+
 const BADGE_BRANCH_DEFAULT: &str = "master";
+const BADGE_BUILD_DEFAULT: &str = "1";
+const BADGE_CODECOV_HOST_DEFAULT: &str = "codecov.io";
+const BADGE_FORGEJO_HOST_DEFAULT: &str = "codeberg.org";
+const BADGE_GITHUB_HOST_DEFAULT: &str = "github.com";
+const BADGE_GITLAB_HOST_DEFAULT: &str = "gitlab.com";
 const BADGE_SERVICE_DEFAULT: &str = "github";
 const BADGE_WORKFLOW_DEFAULT: &str = "main";
 
+const SHIELDS_URL: &str = "https://img.shields.io/";
+/// The styles shields.io can draw a badge in
+const SHIELDS_STYLES: &[&str] = &["flat", "flat-square", "plastic", "for-the-badge", "social"];
+/// The attributes of the badges served by shields.io, see [`style_from_attrs`]
+const STYLE_ATTRIBUTES: &[&str] = &["style", "logo", "label", "color"];
+
+/// The `status` values of the `maintenance` badge documented by the manifest reference
+const MAINTENANCE_STATUSES: &[&str] = &[
+    "actively-developed",
+    "passively-maintained",
+    "as-is",
+    "experimental",
+    "looking-for-maintainer",
+    "deprecated",
+    "none",
+];
+/// The code hosts a `service` attribute can name
+const SERVICES: &[&str] = &["github", "bitbucket", "gitlab"];
+/// The code hosts codecov knows, including the self-hosted ones
+const CODECOV_SERVICES: &[&str] = &[
+    "github",
+    "bitbucket",
+    "gitlab",
+    "github-enterprise",
+    "bitbucket-server",
+    "gitlab-enterprise",
+];
+
 type Attrs = BTreeMap<String, String>;
 
+/// Characters escaped in a path segment or query value: everything but the unreserved ones, so
+/// file names such as `ci.yml` stay readable
+const URL_COMPONENT: &pe::AsciiSet = &pe::NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// The group a badge belongs to, used by the `{{badges.<category>}}` template placeholders.
+///
+/// Categories are ordered as their badges appear in the output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BadgeCategory {
+    /// Where the crate is published.
+    Package,
+    /// Build and test status.
+    Ci,
+    /// Test coverage.
+    Coverage,
+    /// Maintenance status and issue tracking.
+    Maintenance,
+    /// Badges defined in `[package.metadata.readme]`.
+    Custom,
+}
+
+impl BadgeCategory {
+    /// Every category, in the order their badges appear in the output.
+    pub const ALL: &'static [BadgeCategory] = &[
+        BadgeCategory::Package,
+        BadgeCategory::Ci,
+        BadgeCategory::Coverage,
+        BadgeCategory::Maintenance,
+        BadgeCategory::Custom,
+    ];
+
+    /// The name used in `{{badges.<category>}}`.
+    pub fn name(self) -> &'static str {
+        match self {
+            BadgeCategory::Package => "package",
+            BadgeCategory::Ci => "ci",
+            BadgeCategory::Coverage => "coverage",
+            BadgeCategory::Maintenance => "maintenance",
+            BadgeCategory::Custom => "custom",
+        }
+    }
+}
+
+/// A badge rendered from the `[badges]` section, as markdown.
+#[derive(Debug)]
+pub struct Badge {
+    /// The `[badges]` table key it was rendered from.
+    pub key: &'static str,
+    pub category: BadgeCategory,
+    pub markdown: String,
+}
+
+impl AsRef<str> for Badge {
+    fn as_ref(&self) -> &str {
+        &self.markdown
+    }
+}
+
 /// A badge key cargo-readme understands, plus which attributes it reads.
 ///
-/// This is the single source of truth behind `cargo readme --list-badges`. The
-/// `key`s here must stay in sync with the match arms in [`super::manifest::process_badges`];
-/// a unit test in that module enforces it.
+/// Every [`BadgeProvider`](super::provider::BadgeProvider) describes its badge with one, which
+/// `cargo readme --list-badges` prints and the `[badges]` entries are checked against.
 pub struct BadgeInfo {
     /// The `[badges]` table key, e.g. `"coveralls"`.
     pub key: &'static str,
     /// Whether crates.io documents this badge in the manifest reference.
     pub official: bool,
+    /// The group the badge is listed in for `{{badges.<category>}}`.
+    pub category: BadgeCategory,
     /// Attributes that must be present, or badge rendering fails.
     pub required: &'static [&'static str],
     /// Attributes that are read when present, otherwise defaulted.
     pub optional: &'static [&'static str],
+    /// The values accepted by the attributes that do not take free text.
+    pub values: &'static [(&'static str, &'static [&'static str])],
+}
+
+/// How a built-in badge is rendered from the attributes of its `[badges]` entry
+pub type Render = fn(Attrs, &BadgeContext) -> Result<String, String>;
+
+/// A badge cargo-readme renders on its own
+pub struct BuiltinBadge {
+    pub info: BadgeInfo,
+    pub render: Render,
 }
 
 /// Every badge cargo-readme can render, in the order they appear in the output.
-pub const SUPPORTED_BADGES: &[BadgeInfo] = &[
-    BadgeInfo {
-        key: "crates-io",
-        official: false,
-        required: &[],
-        optional: &["crate"],
+pub const SUPPORTED_BADGES: &[BuiltinBadge] = &[
+    BuiltinBadge {
+        info: BadgeInfo {
+            key: "crates-io",
+            official: false,
+            category: BadgeCategory::Package,
+            required: &[],
+            optional: &["crate", "style", "logo", "label", "color"],
+            values: &[],
+        },
+        render: |attrs, cx| crates_io(attrs, cx.name, cx.style),
+    },
+    BuiltinBadge {
+        info: BadgeInfo {
+            key: "appveyor",
+            official: true,
+            category: BadgeCategory::Ci,
+            required: &["repository"],
+            optional: &["branch", "service"],
+            values: &[("service", SERVICES)],
+        },
+        render: |attrs, cx| appveyor(attrs, cx.style),
+    },
+    BuiltinBadge {
+        info: BadgeInfo {
+            key: "circle-ci",
+            official: true,
+            category: BadgeCategory::Ci,
+            required: &["repository"],
+            optional: &["branch", "service"],
+            values: &[("service", SERVICES)],
+        },
+        render: |attrs, cx| circle_ci(attrs, cx.style),
+    },
+    BuiltinBadge {
+        info: BadgeInfo {
+            key: "gitlab",
+            official: true,
+            category: BadgeCategory::Ci,
+            required: &["repository"],
+            optional: &["branch", "host", "instance"],
+            values: &[],
+        },
+        render: |attrs, cx| gitlab(attrs, cx.style),
+    },
+    BuiltinBadge {
+        info: BadgeInfo {
+            key: "travis-ci",
+            official: true,
+            category: BadgeCategory::Ci,
+            required: &["repository"],
+            optional: &["branch"],
+            values: &[],
+        },
+        render: |attrs, cx| travis_ci(attrs, cx.style),
     },
-    BadgeInfo {
-        key: "appveyor",
-        official: true,
-        required: &["repository"],
-        optional: &["branch", "service"],
+    BuiltinBadge {
+        info: BadgeInfo {
+            key: "github",
+            official: false,
+            category: BadgeCategory::Ci,
+            required: &["repository"],
+            optional: &["workflow-file", "branch", "event", "workflow", "host"],
+            values: &[],
+        },
+        render: |attrs, cx| github(attrs, cx.style),
     },
-    BadgeInfo {
-        key: "circle-ci",
-        official: true,
-        required: &["repository"],
-        optional: &["branch", "service"],
+    BuiltinBadge {
+        info: BadgeInfo {
+            key: "azure-devops",
+            official: true,
+            category: BadgeCategory::Ci,
+            required: &["project", "pipeline"],
+            optional: &["build"],
+            values: &[],
+        },
+        render: |attrs, cx| azure_devops(attrs, cx.style),
     },
-    BadgeInfo {
-        key: "gitlab",
-        official: true,
-        required: &["repository"],
-        optional: &["branch"],
+    BuiltinBadge {
+        info: BadgeInfo {
+            key: "bitbucket-pipelines",
+            official: true,
+            category: BadgeCategory::Ci,
+            required: &["repository", "branch"],
+            optional: &["style", "logo", "label", "color"],
+            values: &[],
+        },
+        render: |attrs, cx| bitbucket_pipelines(attrs, cx.style),
     },
-    BadgeInfo {
-        key: "travis-ci",
-        official: true,
-        required: &["repository"],
-        optional: &["branch"],
+    BuiltinBadge {
+        info: BadgeInfo {
+            key: "cirrus-ci",
+            official: true,
+            category: BadgeCategory::Ci,
+            required: &["repository"],
+            optional: &["branch"],
+            values: &[],
+        },
+        render: |attrs, cx| cirrus_ci(attrs, cx.style),
     },
-    BadgeInfo {
-        key: "github",
-        official: false,
-        required: &["repository"],
-        optional: &["workflow"],
+    BuiltinBadge {
+        info: BadgeInfo {
+            key: "gitea",
+            official: false,
+            category: BadgeCategory::Ci,
+            required: &["repository", "workflow-file", "host"],
+            optional: &["branch", "event"],
+            values: &[],
+        },
+        render: |attrs, cx| gitea(attrs, cx.style),
     },
-    BadgeInfo {
-        key: "codecov",
-        official: true,
-        required: &["repository"],
-        optional: &["branch", "service"],
+    BuiltinBadge {
+        info: BadgeInfo {
+            key: "forgejo",
+            official: false,
+            category: BadgeCategory::Ci,
+            required: &["repository", "workflow-file"],
+            optional: &["branch", "event", "host"],
+            values: &[],
+        },
+        render: |attrs, cx| forgejo(attrs, cx.style),
     },
-    BadgeInfo {
-        key: "coveralls",
-        official: true,
-        required: &["repository"],
-        optional: &["branch", "service"],
+    BuiltinBadge {
+        info: BadgeInfo {
+            key: "woodpecker",
+            official: false,
+            category: BadgeCategory::Ci,
+            required: &["repository", "host"],
+            optional: &["branch"],
+            values: &[],
+        },
+        render: |attrs, cx| woodpecker(attrs, cx.style),
     },
-    BadgeInfo {
-        key: "is-it-maintained-issue-resolution",
-        official: true,
-        required: &["repository"],
-        optional: &[],
+    BuiltinBadge {
+        info: BadgeInfo {
+            key: "codecov",
+            official: true,
+            category: BadgeCategory::Coverage,
+            required: &["repository"],
+            optional: &["branch", "service", "host"],
+            values: &[("service", CODECOV_SERVICES)],
+        },
+        render: |attrs, cx| codecov(attrs, cx.style),
     },
-    BadgeInfo {
-        key: "is-it-maintained-open-issues",
-        official: true,
-        required: &["repository"],
-        optional: &[],
+    BuiltinBadge {
+        info: BadgeInfo {
+            key: "coveralls",
+            official: true,
+            category: BadgeCategory::Coverage,
+            required: &["repository"],
+            optional: &["branch", "service"],
+            values: &[("service", SERVICES)],
+        },
+        render: |attrs, cx| coveralls(attrs, cx.style),
     },
-    BadgeInfo {
-        key: "maintenance",
-        official: true,
-        required: &["status"],
-        optional: &[],
+    BuiltinBadge {
+        info: BadgeInfo {
+            key: "is-it-maintained-issue-resolution",
+            official: true,
+            category: BadgeCategory::Maintenance,
+            required: &["repository"],
+            optional: &[],
+            values: &[],
+        },
+        render: |attrs, cx| is_it_maintained_issue_resolution(attrs, cx.style),
+    },
+    BuiltinBadge {
+        info: BadgeInfo {
+            key: "is-it-maintained-open-issues",
+            official: true,
+            category: BadgeCategory::Maintenance,
+            required: &["repository"],
+            optional: &[],
+            values: &[],
+        },
+        render: |attrs, cx| is_it_maintained_open_issues(attrs, cx.style),
+    },
+    BuiltinBadge {
+        info: BadgeInfo {
+            key: "maintenance",
+            official: true,
+            category: BadgeCategory::Maintenance,
+            required: &["status"],
+            optional: &["style", "logo", "label", "color"],
+            values: &[("status", MAINTENANCE_STATUSES)],
+        },
+        render: |attrs, cx| maintenance(attrs, cx.style),
     },
 ];
 
-pub fn appveyor(attrs: Attrs) -> Result<String, String> {
+/// A badge derived from the package metadata alone, enabled by listing its key in `auto-badges`.
+pub struct AutoBadgeInfo {
+    /// The name listed in `auto-badges`, e.g. `"msrv"`.
+    pub key: &'static str,
+    /// The group the badge is listed in for `{{badges.<category>}}`.
+    pub category: BadgeCategory,
+    /// The `Cargo.toml` field the badge is derived from.
+    pub source: &'static str,
+}
+
+/// Every badge that can be derived from the package metadata, in the order they appear in the
+/// output.
+pub const AUTO_BADGES: &[AutoBadgeInfo] = &[
+    AutoBadgeInfo {
+        key: "docs-rs",
+        category: BadgeCategory::Package,
+        source: "package.name",
+    },
+    AutoBadgeInfo {
+        key: "downloads",
+        category: BadgeCategory::Package,
+        source: "package.name",
+    },
+    AutoBadgeInfo {
+        key: "license",
+        category: BadgeCategory::Package,
+        source: "package.license",
+    },
+    AutoBadgeInfo {
+        key: "msrv",
+        category: BadgeCategory::Package,
+        source: "package.rust-version",
+    },
+    AutoBadgeInfo {
+        key: "deps-rs",
+        category: BadgeCategory::Maintenance,
+        source: "package.version",
+    },
+];
+
+pub fn appveyor(attrs: Attrs, style: &BadgeStyle) -> Result<String, String> {
     let repo = required(&attrs, "appveyor", "repository")?;
     let branch = attrs
         .get("branch")
@@ -107,14 +380,21 @@ pub fn appveyor(attrs: Attrs) -> Result<String, String> {
         .map(|i| i.as_ref())
         .unwrap_or(BADGE_SERVICE_DEFAULT);
 
-    Ok(format!(
-        "[![Build Status](https://ci.appveyor.com/api/projects/status/{service}/{repo}?branch={branch}&svg=true)]\
-        (https://ci.appveyor.com/project/{repo}/branch/{branch})",
-        repo=repo, branch=branch, service=service
+    let image = format!(
+        "https://ci.appveyor.com/api/projects/status/{service}/{repo}?branch={branch}&svg=true",
+        repo = repo,
+        branch = branch,
+        service = service
+    );
+    let link = format!("https://ci.appveyor.com/project/{repo}/branch/{branch}");
+    Ok(markdown(
+        "Build Status",
+        &styled_image(&image, style),
+        Some(&link),
     ))
 }
 
-pub fn circle_ci(attrs: Attrs) -> Result<String, String> {
+pub fn circle_ci(attrs: Attrs, style: &BadgeStyle) -> Result<String, String> {
     let repo = required(&attrs, "circle-ci", "repository")?;
     let branch = attrs
         .get("branch")
@@ -127,84 +407,292 @@ pub fn circle_ci(attrs: Attrs) -> Result<String, String> {
             .unwrap_or(BADGE_SERVICE_DEFAULT),
     );
 
-    Ok(format!(
-        "[![Build Status](https://circleci.com/{service}/{repo}/tree/{branch}.svg?style=shield)]\
-         (https://circleci.com/{service}/{repo}/tree/{branch})",
+    let tree = format!(
+        "https://circleci.com/{service}/{repo}/tree/{branch}",
         repo = repo,
         service = service,
         branch = percent_encode(branch)
+    );
+    let image = format!("{tree}.svg?style=shield");
+    Ok(markdown(
+        "Build Status",
+        &styled_image(&image, style),
+        Some(&tree),
     ))
 }
 
-pub fn gitlab(attrs: Attrs) -> Result<String, String> {
+/// GitLab pipeline status, on gitlab.com unless `host` names a self-managed GitLab; `instance` is
+/// the older name of `host`
+pub fn gitlab(attrs: Attrs, style: &BadgeStyle) -> Result<String, String> {
     let repo = required(&attrs, "gitlab", "repository")?;
     let branch = attrs
         .get("branch")
         .map(|i| i.as_ref())
         .unwrap_or(BADGE_BRANCH_DEFAULT);
+    let host = attrs.get("host").or(attrs.get("instance"));
+    let instance = forge_url(host.map_or(BADGE_GITLAB_HOST_DEFAULT, String::as_str));
+    let branch = percent_encode(branch);
 
-    Ok(format!(
-        "[![Build Status](https://gitlab.com/{repo}/badges/{branch}/pipeline.svg)]\
-         (https://gitlab.com/{repo}/commits/master)",
-        repo = repo,
-        branch = percent_encode(branch)
+    let image = format!("{instance}/{repo}/badges/{branch}/pipeline.svg");
+    let link = format!("{instance}/{repo}/commits/{branch}");
+    Ok(markdown(
+        "Build Status",
+        &styled_image(&image, style),
+        Some(&link),
     ))
 }
 
-pub fn travis_ci(attrs: Attrs) -> Result<String, String> {
+pub fn travis_ci(attrs: Attrs, style: &BadgeStyle) -> Result<String, String> {
     let repo = required(&attrs, "travis-ci", "repository")?;
     let branch = attrs
         .get("branch")
         .map(|i| i.as_ref())
         .unwrap_or(BADGE_BRANCH_DEFAULT);
 
-    Ok(format!(
-        "[![Build Status](https://travis-ci.org/{repo}.svg?branch={branch})]\
-         (https://travis-ci.org/{repo})",
-        repo = repo,
+    let image = format!(
+        "https://travis-ci.org/{repo}.svg?branch={branch}",
         branch = percent_encode(branch)
+    );
+    let link = format!("https://travis-ci.org/{repo}");
+    Ok(markdown(
+        "Build Status",
+        &styled_image(&image, style),
+        Some(&link),
     ))
 }
 
-pub fn github(attrs: Attrs) -> Result<String, String> {
+/// GitHub Actions status of a workflow, on github.com unless `host` names a GitHub Enterprise
+/// Server
+///
+/// With `workflow-file`, the badge points at the workflow file, such as `ci.yml`, filtered by the
+/// optional `branch` and `event`. The older `workflow` attribute names the workflow instead, a
+/// form GitHub has deprecated, and is used when `workflow-file` is not set.
+pub fn github(attrs: Attrs, style: &BadgeStyle) -> Result<String, String> {
     let repo = required(&attrs, "github", "repository")?;
+    let github = forge_url(
+        attrs
+            .get("host")
+            .map_or(BADGE_GITHUB_HOST_DEFAULT, String::as_str),
+    );
+
+    if let Some(file) = attrs.get("workflow-file") {
+        let workflow_url = format!(
+            "{github}/{repo}/actions/workflows/{file}",
+            repo = repo,
+            file = pe::utf8_percent_encode(file, URL_COMPONENT)
+        );
+        let link_query = attrs
+            .get("branch")
+            .map(|branch| format!("?query=branch%3A{}", encode_component(branch)))
+            .unwrap_or_default();
+
+        let image = format!("{workflow_url}/badge.svg{}", workflow_query(&attrs));
+        let link = format!("{workflow_url}{link_query}");
+        return Ok(markdown(
+            "Workflow Status",
+            &styled_image(&image, style),
+            Some(&link),
+        ));
+    }
+
     let workflow = attrs
         .get("workflow")
         .map(|i| i.as_ref())
         .unwrap_or(BADGE_WORKFLOW_DEFAULT);
 
-    Ok(format!(
-        "[![Workflow Status](https://github.com/{repo}/workflows/{workflow}/badge.svg)]\
-         (https://github.com/{repo}/actions?query=workflow%3A%22{workflow_plus}%22)",
-        repo = repo,
-        workflow = percent_encode(workflow),
+    let image = format!(
+        "{github}/{repo}/workflows/{workflow}/badge.svg",
+        workflow = percent_encode(workflow)
+    );
+    let link = format!(
+        "{github}/{repo}/actions?query=workflow%3A%22{workflow_plus}%22",
         workflow_plus = percent_encode(&str::replace(workflow, " ", "+"))
+    );
+    Ok(markdown(
+        "Workflow Status",
+        &styled_image(&image, style),
+        Some(&link),
+    ))
+}
+
+/// Azure Pipelines status, `project` being `organization/project` and `build` the id of the
+/// build definition
+pub fn azure_devops(attrs: Attrs, style: &BadgeStyle) -> Result<String, String> {
+    let project = required(&attrs, "azure-devops", "project")?;
+    let pipeline = required(&attrs, "azure-devops", "pipeline")?;
+    let build = attrs
+        .get("build")
+        .map(|i| i.as_ref())
+        .unwrap_or(BADGE_BUILD_DEFAULT);
+
+    let image = format!(
+        "https://dev.azure.com/{project}/_apis/build/status/{pipeline}",
+        pipeline = pe::utf8_percent_encode(pipeline, URL_COMPONENT)
+    );
+    let link = format!(
+        "https://dev.azure.com/{project}/_build/latest?definitionId={build}",
+        build = percent_encode(build)
+    );
+    Ok(markdown(
+        "Build Status",
+        &styled_image(&image, style),
+        Some(&link),
+    ))
+}
+
+pub fn bitbucket_pipelines(attrs: Attrs, style: &BadgeStyle) -> Result<String, String> {
+    let repo = required(&attrs, "bitbucket-pipelines", "repository")?;
+    let branch = required(&attrs, "bitbucket-pipelines", "branch")?;
+    let branch = percent_encode(branch);
+
+    let image = format!("https://img.shields.io/bitbucket/pipelines/{repo}/{branch}.svg");
+    let link = format!(
+        "https://bitbucket.org/{repo}/addon/pipelines/home#!/results/branch/{branch}/page/1"
+    );
+    Ok(markdown(
+        "Build Status",
+        &styled_image(&image, style),
+        Some(&link),
+    ))
+}
+
+pub fn cirrus_ci(attrs: Attrs, style: &BadgeStyle) -> Result<String, String> {
+    let repo = required(&attrs, "cirrus-ci", "repository")?;
+    let branch = attrs
+        .get("branch")
+        .map(|i| i.as_ref())
+        .unwrap_or(BADGE_BRANCH_DEFAULT);
+
+    let image = format!(
+        "https://api.cirrus-ci.com/github/{repo}.svg?branch={branch}",
+        branch = percent_encode(branch)
+    );
+    let link = format!("https://cirrus-ci.com/github/{repo}");
+    Ok(markdown(
+        "Build Status",
+        &styled_image(&image, style),
+        Some(&link),
     ))
 }
 
-pub fn codecov(attrs: Attrs) -> Result<String, String> {
+/// Gitea Actions workflow status, on the Gitea instance named by `host`
+pub fn gitea(attrs: Attrs, style: &BadgeStyle) -> Result<String, String> {
+    let host = required(&attrs, "gitea", "host")?;
+    actions_workflow("gitea", host, &attrs, style)
+}
+
+/// Forgejo Actions workflow status, on codeberg.org unless `host` names another Forgejo instance
+pub fn forgejo(attrs: Attrs, style: &BadgeStyle) -> Result<String, String> {
+    let host = attrs
+        .get("host")
+        .map_or(BADGE_FORGEJO_HOST_DEFAULT, String::as_str);
+    actions_workflow("forgejo", host, &attrs, style)
+}
+
+/// Workflow status of Gitea and Forgejo, which serve the same badges
+fn actions_workflow(
+    badge: &str,
+    host: &str,
+    attrs: &Attrs,
+    style: &BadgeStyle,
+) -> Result<String, String> {
+    let repo = required(attrs, badge, "repository")?;
+    let file = required(attrs, badge, "workflow-file")?;
+
+    let repo_url = format!("{}/{repo}", forge_url(host));
+    let file = pe::utf8_percent_encode(file, URL_COMPONENT);
+    let image = format!(
+        "{repo_url}/actions/workflows/{file}/badge.svg{}",
+        workflow_query(attrs)
+    );
+    let link = format!("{repo_url}/actions?workflow={file}");
+    Ok(markdown(
+        "Workflow Status",
+        &styled_image(&image, style),
+        Some(&link),
+    ))
+}
+
+/// The query of an Actions workflow badge, filtering the runs by the `branch` and `event`
+/// attributes, or nothing when neither is set
+///
+/// GitHub, Gitea and Forgejo read the same parameters.
+fn workflow_query(attrs: &Attrs) -> String {
+    let query: Vec<String> = ["branch", "event"]
+        .into_iter()
+        .filter_map(|name| {
+            let value = attrs.get(name)?;
+            Some(format!("{name}={}", encode_component(value)))
+        })
+        .collect();
+    if query.is_empty() {
+        String::new()
+    } else {
+        format!("?{}", query.join("&"))
+    }
+}
+
+/// Woodpecker CI pipeline status, on the server named by `host`; `repository` is the id of the
+/// repository on that server, or its `owner/repo` for older servers
+pub fn woodpecker(attrs: Attrs, style: &BadgeStyle) -> Result<String, String> {
+    let repo = required(&attrs, "woodpecker", "repository")?;
+    let server = forge_url(required(&attrs, "woodpecker", "host")?);
+
+    let image = match attrs.get("branch") {
+        Some(branch) => format!(
+            "{server}/api/badges/{repo}/status.svg?branch={}",
+            percent_encode(branch)
+        ),
+        None => format!("{server}/api/badges/{repo}/status.svg"),
+    };
+    let link = format!("{server}/repos/{repo}");
+    Ok(markdown(
+        "Build Status",
+        &styled_image(&image, style),
+        Some(&link),
+    ))
+}
+
+/// Codecov coverage, on codecov.io unless `host` names a self-hosted Codecov instance
+///
+/// The self-hosted forges among the `service`s are only known to a self-hosted instance, so they
+/// need a `host`.
+pub fn codecov(attrs: Attrs, style: &BadgeStyle) -> Result<String, String> {
     let repo = required(&attrs, "codecov", "repository")?;
     let branch = attrs
         .get("branch")
         .map(|i| i.as_ref())
         .unwrap_or(BADGE_BRANCH_DEFAULT);
-    let service = badge_service_short_name(
-        attrs
-            .get("service")
-            .map(|i| i.as_ref())
-            .unwrap_or(BADGE_SERVICE_DEFAULT),
+    let service = attrs
+        .get("service")
+        .map(|i| i.as_ref())
+        .unwrap_or(BADGE_SERVICE_DEFAULT);
+    let host = attrs.get("host");
+    let self_hosted_forge = matches!(
+        service,
+        "github-enterprise" | "bitbucket-server" | "gitlab-enterprise"
     );
+    if self_hosted_forge && host.is_none() {
+        return Err(format!(
+            "badge `codecov` with `service` `{service}` needs the `host` of a self-hosted Codecov"
+        ));
+    }
+    let codecov = forge_url(host.map_or(BADGE_CODECOV_HOST_DEFAULT, String::as_str));
+    let service = badge_service_short_name(service);
 
-    Ok(format!(
-        "[![Coverage Status](https://codecov.io/{service}/{repo}/branch/{branch}/graph/badge.svg)]\
-         (https://codecov.io/{service}/{repo})",
-        repo = repo,
-        branch = percent_encode(branch),
-        service = service
+    let image = format!(
+        "{codecov}/{service}/{repo}/branch/{branch}/graph/badge.svg",
+        branch = percent_encode(branch)
+    );
+    let link = format!("{codecov}/{service}/{repo}");
+    Ok(markdown(
+        "Coverage Status",
+        &styled_image(&image, style),
+        Some(&link),
     ))
 }
 
-pub fn coveralls(attrs: Attrs) -> Result<String, String> {
+pub fn coveralls(attrs: Attrs, style: &BadgeStyle) -> Result<String, String> {
     let repo = required(&attrs, "coveralls", "repository")?;
     let branch = attrs
         .get("branch")
@@ -214,35 +702,45 @@ pub fn coveralls(attrs: Attrs) -> Result<String, String> {
         .get("service")
         .map(|i| i.as_ref())
         .unwrap_or(BADGE_SERVICE_DEFAULT);
+    let branch = percent_encode(branch);
 
-    Ok(format!(
-        "[![Coverage Status](https://coveralls.io/repos/{service}/{repo}/badge.svg?branch={branch})]\
-         (https://coveralls.io/{service}/{repo}?branch={branch})",
-        repo = repo,
-        branch = percent_encode(branch),
-        service = service
+    let image = format!("https://coveralls.io/repos/{service}/{repo}/badge.svg?branch={branch}");
+    let link = format!("https://coveralls.io/{service}/{repo}?branch={branch}");
+    Ok(markdown(
+        "Coverage Status",
+        &styled_image(&image, style),
+        Some(&link),
     ))
 }
 
-pub fn is_it_maintained_issue_resolution(attrs: Attrs) -> Result<String, String> {
+pub fn is_it_maintained_issue_resolution(
+    attrs: Attrs,
+    style: &BadgeStyle,
+) -> Result<String, String> {
     let repo = required(&attrs, "is-it-maintained-issue-resolution", "repository")?;
-    Ok(format!(
-        "[![Average time to resolve an issue](https://isitmaintained.com/badge/resolution/{repo}.svg)]\
-        (https://isitmaintained.com/project/{repo} \"Average time to resolve an issue\")",
-        repo=repo
+    let image = format!("https://isitmaintained.com/badge/resolution/{repo}.svg");
+    let link =
+        format!("https://isitmaintained.com/project/{repo} \"Average time to resolve an issue\"");
+    Ok(markdown(
+        "Average time to resolve an issue",
+        &styled_image(&image, style),
+        Some(&link),
     ))
 }
 
-pub fn is_it_maintained_open_issues(attrs: Attrs) -> Result<String, String> {
+pub fn is_it_maintained_open_issues(attrs: Attrs, style: &BadgeStyle) -> Result<String, String> {
     let repo = required(&attrs, "is-it-maintained-open-issues", "repository")?;
-    Ok(format!(
-        "[![Percentage of issues still open](https://isitmaintained.com/badge/open/{repo}.svg)]\
-         (https://isitmaintained.com/project/{repo} \"Percentage of issues still open\")",
-        repo = repo
+    let image = format!("https://isitmaintained.com/badge/open/{repo}.svg");
+    let link =
+        format!("https://isitmaintained.com/project/{repo} \"Percentage of issues still open\"");
+    Ok(markdown(
+        "Percentage of issues still open",
+        &styled_image(&image, style),
+        Some(&link),
     ))
 }
 
-pub fn maintenance(attrs: Attrs) -> Result<String, String> {
+pub fn maintenance(attrs: Attrs, style: &BadgeStyle) -> Result<String, String> {
     let status = required(&attrs, "maintenance", "status")?;
 
     // https://github.com/rust-lang/crates.io/blob/5a08887d4b531e034d01386d3e5997514f3c8ee5/src/models/badge.rs#L82
@@ -254,26 +752,342 @@ pub fn maintenance(attrs: Attrs) -> Result<String, String> {
         "experimental" => "experimental-blue",
         "looking-for-maintainer" => "looking--for--maintainer-darkblue", // color is a guess
         "deprecated" => "deprecated-red",
+        // reported by `check_badge`
         _ => "unknown-black",
     };
 
     //example https://img.shields.io/badge/maintenance-experimental-blue.svg
-    Ok(format!(
-        "![Maintenance](https://img.shields.io/badge/maintenance-{status}.svg)",
-        status = status_with_color
-    ))
+    let image = format!("https://img.shields.io/badge/maintenance-{status_with_color}.svg");
+    Ok(markdown("Maintenance", &styled_image(&image, style), None))
 }
 
-pub fn crates_io(attrs: Attrs, crate_name: &str) -> Result<String, String> {
+pub fn crates_io(attrs: Attrs, crate_name: &str, style: &BadgeStyle) -> Result<String, String> {
     // Not part of the official `[badges]` set, but the crate name is already in
     // `Cargo.toml`, so `crate` defaults to the package name when omitted.
     let name = attrs.get("crate").map(|s| s.as_ref()).unwrap_or(crate_name);
-    Ok(format!(
-        "[![Crates.io](https://img.shields.io/crates/v/{name}.svg)](https://crates.io/crates/{name})",
-        name = name
+    let image = format!("https://img.shields.io/crates/v/{name}.svg");
+    let link = format!("https://crates.io/crates/{name}");
+    Ok(markdown(
+        "Crates.io",
+        &styled_image(&image, style),
+        Some(&link),
     ))
 }
 
+pub fn docs_rs(crate_name: &str, style: &BadgeStyle) -> String {
+    let image = format!("https://docs.rs/{crate_name}/badge.svg");
+    let link = format!("https://docs.rs/{crate_name}");
+    markdown("Documentation", &styled_image(&image, style), Some(&link))
+}
+
+pub fn downloads(crate_name: &str, style: &BadgeStyle) -> String {
+    let image = format!("https://img.shields.io/crates/d/{crate_name}.svg");
+    let link = format!("https://crates.io/crates/{crate_name}");
+    markdown("Downloads", &styled_image(&image, style), Some(&link))
+}
+
+/// The license expression as is, e.g. `MIT OR Apache-2.0`
+pub fn license(license: &str, style: &BadgeStyle) -> String {
+    let image = format!(
+        "https://img.shields.io/badge/license-{}-blue.svg",
+        shields_escape(license)
+    );
+    markdown(
+        &format!("License: {license}"),
+        &styled_image(&image, style),
+        None,
+    )
+}
+
+/// The minimum supported Rust version, from `rust-version`
+pub fn msrv(rust_version: &str, style: &BadgeStyle) -> String {
+    let image = format!(
+        "https://img.shields.io/badge/rustc-{}%2B-blue.svg",
+        shields_escape(rust_version)
+    );
+    markdown(
+        &format!("MSRV: {rust_version}"),
+        &styled_image(&image, style),
+        None,
+    )
+}
+
+pub fn deps_rs(crate_name: &str, version: &str, style: &BadgeStyle) -> String {
+    let crate_url = format!(
+        "https://deps.rs/crate/{crate_name}/{version}",
+        version = pe::utf8_percent_encode(version, URL_COMPONENT)
+    );
+    let image = format!("{crate_url}/status.svg");
+    markdown(
+        "Dependency Status",
+        &styled_image(&image, style),
+        Some(&crate_url),
+    )
+}
+
+/// A badge defined in `[package.metadata.readme]`
+///
+/// `{name}`, `{version}` and `{repository}` in its fields are replaced with the package's. The
+/// style set on the badge itself takes precedence over `style`.
+pub fn custom(
+    badge: &CustomBadge,
+    name: &str,
+    version: &str,
+    repository: Option<&str>,
+    style: &BadgeStyle,
+) -> Result<String, String> {
+    let interpolate = |field: &str| -> Result<String, String> {
+        let mut value = field.replace("{name}", name).replace("{version}", version);
+        if value.contains("{repository}") {
+            let repository = repository.ok_or_else(|| {
+                format!(
+                    "custom badge `{}` uses `{{repository}}` but `package.repository` is not set",
+                    badge.alt
+                )
+            })?;
+            value = value.replace("{repository}", repository);
+        }
+        Ok(value)
+    };
+
+    let style = badge.style().or(style.clone());
+    check_style(&style)?;
+
+    let alt = interpolate(&badge.alt)?;
+    let image = styled_image(&interpolate(&badge.image)?, &style);
+    let image = match badge.title.as_deref() {
+        Some(title) => format!("{image} \"{}\"", interpolate(title)?),
+        None => image,
+    };
+    let link = badge.link.as_deref().map(interpolate).transpose()?;
+    Ok(markdown(&alt, &image, link.as_deref()))
+}
+
+/// Values for the attributes a `[badges]` entry leaves out, derived from the package
+///
+/// The `repository` of a badge defaults to the slug of `package.repository`, when the badge
+/// can point to its host, and so does `service` for the badges that have one. The `branch`
+/// defaults to the default branch of the local clone, when the clone's remote is the badge's
+/// repository.
+#[derive(Debug, Default)]
+pub struct BadgeDefaults {
+    /// The host and `owner/repo` slug of `package.repository`.
+    pub repository: Option<(Forge, String)>,
+    /// The `owner/repo` slug of the remote of the local clone, and its default branch.
+    pub clone: Option<(String, String)>,
+}
+
+impl BadgeDefaults {
+    /// Fill in the attributes of a badge that are missing and can be derived
+    pub fn apply(&self, info: &BadgeInfo, attrs: &mut Attrs) {
+        let (forges, defaults_branch) = inferred_attributes(info);
+
+        // a badge for a self-hosted forge is not about the repository on the public one
+        let self_hosted = attrs.contains_key("host") || attrs.contains_key("instance");
+        if let Some((forge, slug)) = self.repository.as_ref().filter(|_| !self_hosted) {
+            if forges.contains(forge) && !attrs.contains_key("repository") {
+                attrs.insert("repository".to_owned(), slug.clone());
+                if let Some(service) = forge.service().filter(|_| reads(info, "service")) {
+                    attrs
+                        .entry("service".to_owned())
+                        .or_insert_with(|| service.to_owned());
+                }
+            }
+        }
+
+        if let (true, Some((slug, branch))) = (defaults_branch, &self.clone) {
+            let same_repository = attrs
+                .get("repository")
+                .is_some_and(|repository| repository.eq_ignore_ascii_case(slug));
+            if same_repository && !attrs.contains_key("branch") {
+                attrs.insert("branch".to_owned(), branch.clone());
+            }
+        }
+    }
+}
+
+/// The hosts a badge can take its `repository` from, and whether it has a default `branch`
+///
+/// The badges of other providers take them from the attributes they read.
+fn inferred_attributes(info: &BadgeInfo) -> (&'static [Forge], bool) {
+    const SERVICE_FORGES: &[Forge] = &[Forge::GitHub, Forge::Bitbucket, Forge::GitLab];
+    const ALL_FORGES: &[Forge] = &[
+        Forge::GitHub,
+        Forge::GitLab,
+        Forge::Bitbucket,
+        Forge::Codeberg,
+    ];
+    match info.key {
+        "appveyor" | "circle-ci" | "codecov" | "coveralls" => (SERVICE_FORGES, true),
+        "gitlab" => (&[Forge::GitLab], true),
+        "bitbucket-pipelines" => (&[Forge::Bitbucket], true),
+        "forgejo" => (&[Forge::Codeberg], false),
+        "travis-ci" | "cirrus-ci" => (&[Forge::GitHub], true),
+        // the `branch` of `github` filters the runs instead of defaulting to a branch
+        "github" | "is-it-maintained-issue-resolution" | "is-it-maintained-open-issues" => {
+            (&[Forge::GitHub], false)
+        }
+        "crates-io" | "azure-devops" | "gitea" | "woodpecker" | "maintenance" => (&[], false),
+        _ if reads(info, "repository") => (ALL_FORGES, reads(info, "branch")),
+        _ => (&[], false),
+    }
+}
+
+/// Does the badge read the attribute `name`?
+fn reads(info: &BadgeInfo, name: &str) -> bool {
+    info.required.contains(&name) || info.optional.contains(&name)
+}
+
+/// Find what is wrong with a `[badges]` entry that rendering would silently ignore
+///
+/// Returns a message for an unknown badge key, for each unknown attribute, and for each
+/// attribute whose value is not one it accepts. Missing required attributes are left to the
+/// renderers, which fail on them.
+pub fn check_badge(registry: &BadgeRegistry, key: &str, attrs: &Attrs) -> Vec<String> {
+    let Some((_, provider)) = registry.get(key) else {
+        let keys: Vec<&str> = registry.providers().map(|p| p.info().key).collect();
+        let mut msg = format!("unknown badge `{key}` in [badges]");
+        if AUTO_BADGES.iter().any(|auto| auto.key == key) {
+            msg.push_str(&format!(
+                " (`{key}` is derived from the package, list it in `auto-badges`)"
+            ));
+        } else if let Some(known) = suggest_name(key, keys.iter().copied()) {
+            msg.push_str(&format!(" (did you mean `{known}`?)"));
+        }
+        return vec![msg];
+    };
+    let info = provider.info();
+
+    let known = info.required.iter().chain(info.optional).copied();
+    let mut problems = Vec::new();
+    for (name, value) in attrs {
+        if !known.clone().any(|attr| attr == name) && STYLE_ATTRIBUTES.contains(&name.as_str()) {
+            problems.push(format!(
+                "badge `{key}` has `{name}`, but only badges served by shields.io can be styled"
+            ));
+            continue;
+        }
+        if !known.clone().any(|attr| attr == name) {
+            let mut msg = format!("badge `{key}` has unknown attribute `{name}`");
+            if let Some(attr) = suggest_name(name, known.clone()) {
+                msg.push_str(&format!(" (did you mean `{attr}`?)"));
+            }
+            problems.push(msg);
+            continue;
+        }
+
+        let values = info
+            .values
+            .iter()
+            .find(|(attr, _)| attr == name)
+            .map(|(_, values)| *values);
+        if let Some(values) = values.filter(|values| !values.contains(&value.as_str())) {
+            let mut msg = format!(
+                "badge `{key}` has invalid `{name}` `{value}`, expected one of: {}",
+                values.join(", ")
+            );
+            if let Some(known) = suggest_name(value, values.iter().copied()) {
+                msg.push_str(&format!(" (did you mean `{known}`?)"));
+            }
+            problems.push(msg);
+        }
+    }
+    problems
+}
+
+/// A known name close to `name`, or one that `name` abbreviates, such as `repository` for `repo`
+fn suggest_name<'a>(name: &str, known: impl Iterator<Item = &'a str> + Clone) -> Option<&'a str> {
+    let normalized = name.replace('_', "-");
+    suggest::did_you_mean(&normalized, known.clone()).or_else(|| {
+        let mut abbreviated =
+            known.filter(|known| normalized.len() >= 3 && known.starts_with(&normalized));
+        // only a single match is a plausible abbreviation
+        match (abbreviated.next(), abbreviated.next()) {
+            (Some(known), None) => Some(known),
+            _ => None,
+        }
+    })
+}
+
+/// The style attributes of a `[badges]` entry
+pub fn style_from_attrs(attrs: &Attrs) -> BadgeStyle {
+    BadgeStyle {
+        style: attrs.get("style").cloned(),
+        logo: attrs.get("logo").cloned(),
+        label: attrs.get("label").cloned(),
+        color: attrs.get("color").cloned(),
+    }
+}
+
+/// Check that the `style` of a badge is one shields.io knows
+pub fn check_style(style: &BadgeStyle) -> Result<(), String> {
+    match style.style.as_deref() {
+        Some(name) if !SHIELDS_STYLES.contains(&name) => {
+            let mut msg = format!(
+                "Unknown badge style `{name}`, expected one of: {}",
+                SHIELDS_STYLES.join(", ")
+            );
+            if let Some(known) = suggest::did_you_mean(name, SHIELDS_STYLES.iter().copied()) {
+                msg.push_str(&format!(" (did you mean `{known}`?)"));
+            }
+            Err(msg)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Add the query parameters of `style` to a shields.io image URL
+///
+/// Other services have no such parameters, so their images are left as they are.
+fn styled_image(image: &str, style: &BadgeStyle) -> String {
+    if !image.starts_with(SHIELDS_URL) {
+        return image.to_owned();
+    }
+
+    let params = [
+        ("style", &style.style),
+        ("logo", &style.logo),
+        ("label", &style.label),
+        ("color", &style.color),
+    ];
+    let query: Vec<String> = params
+        .iter()
+        .filter_map(|(name, value)| {
+            let value = value.as_deref()?;
+            Some(format!(
+                "{name}={}",
+                pe::utf8_percent_encode(value, URL_COMPONENT)
+            ))
+        })
+        .collect();
+
+    if query.is_empty() {
+        image.to_owned()
+    } else if image.contains('?') {
+        format!("{image}&{}", query.join("&"))
+    } else {
+        format!("{image}?{}", query.join("&"))
+    }
+}
+
+/// Markdown of a badge image, linked when there is a `link`
+fn markdown(alt: &str, image: &str, link: Option<&str>) -> String {
+    match link {
+        Some(link) => format!("[![{alt}]({image})]({link})"),
+        None => format!("![{alt}]({image})"),
+    }
+}
+
+/// The base URL of a forge, from a host name or a URL
+fn forge_url(host: &str) -> String {
+    let host = host.trim_end_matches('/');
+    if host.contains("://") {
+        host.to_owned()
+    } else {
+        format!("https://{host}")
+    }
+}
+
 /// Look up an attribute that a badge cannot render without.
 fn required<'a>(attrs: &'a Attrs, badge: &str, key: &str) -> Result<&'a str, String> {
     attrs
@@ -282,15 +1096,217 @@ fn required<'a>(attrs: &'a Attrs, badge: &str, key: &str) -> Result<&'a str, Str
         .ok_or_else(|| format!("badge `{badge}` is missing required attribute `{key}`"))
 }
 
+/// Escape text for a shields.io static badge, where `-` and `_` separate the parts of the badge
+fn shields_escape(text: &str) -> String {
+    let text = text.replace('-', "--").replace('_', "__");
+    pe::utf8_percent_encode(&text, URL_COMPONENT).to_string()
+}
+
 fn percent_encode(input: &str) -> pe::PercentEncode<'_> {
     pe::utf8_percent_encode(input, pe::NON_ALPHANUMERIC)
 }
 
+/// Escape a value for a URL query or path segment
+fn encode_component(value: &str) -> String {
+    pe::utf8_percent_encode(value, URL_COMPONENT).to_string()
+}
+
 fn badge_service_short_name(service: &str) -> &'static str {
     match service {
         "github" => "gh",
         "bitbucket" => "bb",
         "gitlab" => "gl",
+        "github-enterprise" => "ghe",
+        "bitbucket-server" => "bbs",
+        "gitlab-enterprise" => "gle",
         _ => "gh",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gitlab_instance() {
+        let attrs = |instance: &str| {
+            Attrs::from([
+                ("repository".to_owned(), "group/project".to_owned()),
+                ("instance".to_owned(), instance.to_owned()),
+            ])
+        };
+        let expected = "[![Build Status](https://git.example.com/group/project/badges/master/\
+                        pipeline.svg)](https://git.example.com/group/project/commits/master)";
+        let style = BadgeStyle::default();
+        assert_eq!(expected, gitlab(attrs("git.example.com"), &style).unwrap());
+        assert_eq!(
+            expected,
+            gitlab(attrs("https://git.example.com/"), &style).unwrap()
+        );
+    }
+
+    #[test]
+    fn style_shields_images_only() {
+        let style = BadgeStyle {
+            style: Some("for-the-badge".to_owned()),
+            logo: Some("rust".to_owned()),
+            ..BadgeStyle::default()
+        };
+        assert_eq!(
+            "[![Documentation](https://docs.rs/some-crate/badge.svg)](https://docs.rs/some-crate)",
+            docs_rs("some-crate", &style)
+        );
+        assert_eq!(
+            "[![Downloads](https://img.shields.io/crates/d/some-crate.svg?style=for-the-badge&\
+             logo=rust)](https://crates.io/crates/some-crate)",
+            downloads("some-crate", &style)
+        );
+    }
+
+    #[test]
+    fn unknown_style_should_fail() {
+        let style = BadgeStyle {
+            style: Some("flat-squar".to_owned()),
+            ..BadgeStyle::default()
+        };
+        assert_eq!(
+            "Unknown badge style `flat-squar`, expected one of: flat, flat-square, plastic, \
+             for-the-badge, social (did you mean `flat-square`?)",
+            check_style(&style).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn check_badge_suggestions() {
+        let attrs = |pairs: &[(&str, &str)]| -> Attrs {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        assert_eq!(
+            vec!["badge `github` has unknown attribute `repo` (did you mean `repository`?)"],
+            check_badge(&BadgeRegistry::new(), "github", &attrs(&[("repo", "x/y")]))
+        );
+        assert_eq!(
+            vec![
+                "unknown badge `docs-rs` in [badges] (`docs-rs` is derived from the package, \
+                  list it in `auto-badges`)"
+            ],
+            check_badge(&BadgeRegistry::new(), "docs-rs", &Attrs::new())
+        );
+        assert!(check_badge(
+            &BadgeRegistry::new(),
+            "codecov",
+            &attrs(&[("repository", "x/y"), ("service", "gitlab")])
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn check_badge_style_on_shields_badges_only() {
+        let attrs = |pairs: &[(&str, &str)]| -> Attrs {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        assert_eq!(
+            vec!["badge `codecov` has `style`, but only badges served by shields.io can be styled"],
+            check_badge(
+                &BadgeRegistry::new(),
+                "codecov",
+                &attrs(&[("repository", "x/y"), ("style", "flat")])
+            )
+        );
+        assert!(check_badge(
+            &BadgeRegistry::new(),
+            "maintenance",
+            &attrs(&[("status", "experimental"), ("style", "flat")])
+        )
+        .is_empty());
+    }
+
+    fn info(key: &str) -> &'static BadgeInfo {
+        &SUPPORTED_BADGES
+            .iter()
+            .find(|badge| badge.info.key == key)
+            .unwrap()
+            .info
+    }
+
+    #[test]
+    fn defaults_from_repository_and_clone() {
+        let defaults = BadgeDefaults {
+            repository: Some((Forge::GitHub, "owner/repo".to_owned())),
+            clone: Some(("Owner/Repo".to_owned(), "main".to_owned())),
+        };
+
+        let mut travis = Attrs::new();
+        defaults.apply(info("travis-ci"), &mut travis);
+        assert_eq!(
+            Attrs::from([
+                ("branch".to_owned(), "main".to_owned()),
+                ("repository".to_owned(), "owner/repo".to_owned()),
+            ]),
+            travis
+        );
+
+        // a badge for another repository keeps the default branch of its renderer
+        let mut other = Attrs::from([("repository".to_owned(), "other/repo".to_owned())]);
+        defaults.apply(info("codecov"), &mut other);
+        assert_eq!(None, other.get("branch"));
+        assert_eq!(None, other.get("service"));
+
+        // a GitHub repository is no GitLab project
+        let mut gitlab = Attrs::new();
+        defaults.apply(info("gitlab"), &mut gitlab);
+        assert!(gitlab.is_empty());
+    }
+
+    #[test]
+    fn codecov_self_hosted_service_needs_host() {
+        let style = BadgeStyle::default();
+        let mut attrs = Attrs::from([
+            ("repository".to_owned(), "group/project".to_owned()),
+            ("service".to_owned(), "gitlab-enterprise".to_owned()),
+        ]);
+        assert_eq!(
+            "badge `codecov` with `service` `gitlab-enterprise` needs the `host` of a self-hosted \
+             Codecov",
+            codecov(attrs.clone(), &style).unwrap_err()
+        );
+
+        attrs.insert("host".to_owned(), "codecov.example.com".to_owned());
+        let expected = "[![Coverage Status](https://codecov.example.com/gle/group/project/branch/\
+                        master/graph/badge.svg)](https://codecov.example.com/gle/group/project)";
+        assert_eq!(expected, codecov(attrs, &style).unwrap());
+    }
+
+    #[test]
+    fn defaults_for_other_providers() {
+        let defaults = BadgeDefaults {
+            repository: Some((Forge::GitLab, "group/project".to_owned())),
+            clone: Some(("group/project".to_owned(), "main".to_owned())),
+        };
+        let chat = BadgeInfo {
+            key: "chat",
+            official: false,
+            category: BadgeCategory::Custom,
+            required: &["repository"],
+            optional: &["branch", "service"],
+            values: &[],
+        };
+
+        let mut attrs = Attrs::new();
+        defaults.apply(&chat, &mut attrs);
+        assert_eq!(
+            Attrs::from([
+                ("branch".to_owned(), "main".to_owned()),
+                ("repository".to_owned(), "group/project".to_owned()),
+                ("service".to_owned(), "gitlab".to_owned()),
+            ]),
+            attrs
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! Recognize the code hosts behind repository URLs
+
+/// A code host whose repositories badges can point to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Forge {
+    GitHub,
+    GitLab,
+    Bitbucket,
+    Codeberg,
+}
+
+impl Forge {
+    fn from_host(host: &str) -> Option<Forge> {
+        match host
+            .trim_start_matches("www.")
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "github.com" => Some(Forge::GitHub),
+            "gitlab.com" => Some(Forge::GitLab),
+            "bitbucket.org" => Some(Forge::Bitbucket),
+            "codeberg.org" => Some(Forge::Codeberg),
+            _ => None,
+        }
+    }
+
+    /// The value of the `service` attribute of the badges that can point to several hosts
+    pub fn service(self) -> Option<&'static str> {
+        match self {
+            Forge::GitHub => Some("github"),
+            Forge::GitLab => Some("gitlab"),
+            Forge::Bitbucket => Some("bitbucket"),
+            Forge::Codeberg => None,
+        }
+    }
+}
+
+/// Find the host and the `owner/repo` slug of a repository URL
+///
+/// Accepts web URLs, with or without a trailing `.git` or a path into the repository such as
+/// `/tree/main`, as well as `ssh://` and `git@host:owner/repo` remotes. GitLab slugs keep their
+/// subgroups, as in `group/subgroup/project`. Other hosts are not recognized.
+pub fn parse_repository_url(url: &str) -> Option<(Forge, String)> {
+    let url = url.trim();
+    let (host, path) = match url.split_once("://") {
+        Some((_, rest)) => {
+            let (authority, path) = rest.split_once('/')?;
+            let host = authority.rsplit('@').next()?;
+            // drop the port
+            let host = host.split(':').next()?;
+            (host, path)
+        }
+        // scp-like syntax, `git@github.com:owner/repo.git`
+        None => {
+            let (authority, path) = url.split_once(':')?;
+            (authority.rsplit('@').next()?, path)
+        }
+    };
+    let forge = Forge::from_host(host)?;
+
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let segments: Vec<&str> = match forge {
+        // GitLab puts `/-/` between the project and the pages inside it
+        Forge::GitLab => segments
+            .iter()
+            .take_while(|s| **s != "-")
+            .copied()
+            .collect(),
+        _ => segments.into_iter().take(2).collect(),
+    };
+    if segments.len() < 2 {
+        return None;
+    }
+
+    Some((forge, segments.join("/")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_web_and_remote_urls() {
+        let github = Some((Forge::GitHub, "owner/repo".to_owned()));
+        assert_eq!(
+            github,
+            parse_repository_url("https://github.com/owner/repo")
+        );
+        assert_eq!(
+            github,
+            parse_repository_url("https://www.github.com/owner/repo/")
+        );
+        assert_eq!(
+            github,
+            parse_repository_url("https://github.com/owner/repo.git")
+        );
+        assert_eq!(
+            github,
+            parse_repository_url("https://github.com/owner/repo/tree/main/crates/core")
+        );
+        assert_eq!(
+            github,
+            parse_repository_url("git@github.com:owner/repo.git")
+        );
+        assert_eq!(
+            github,
+            parse_repository_url("ssh://git@github.com:22/owner/repo.git")
+        );
+        assert_eq!(
+            Some((Forge::Codeberg, "owner/repo".to_owned())),
+            parse_repository_url("https://codeberg.org/owner/repo")
+        );
+        assert_eq!(
+            Some((Forge::Bitbucket, "owner/repo".to_owned())),
+            parse_repository_url("https://bitbucket.org/owner/repo/src/main/")
+        );
+    }
+
+    #[test]
+    fn parse_gitlab_subgroups() {
+        assert_eq!(
+            Some((Forge::GitLab, "group/subgroup/project".to_owned())),
+            parse_repository_url("https://gitlab.com/group/subgroup/project/-/tree/main")
+        );
+    }
+
+    #[test]
+    fn unknown_hosts_and_short_paths() {
+        assert_eq!(None, parse_repository_url("https://example.com/owner/repo"));
+        assert_eq!(None, parse_repository_url("https://github.com/owner"));
+    }
+}
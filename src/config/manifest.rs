@@ -4,20 +4,94 @@ use serde::Deserialize;
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
-use super::badges;
+use super::badges::{self, Badge, BadgeCategory, BadgeDefaults};
+use super::forge;
+use super::git::{self, GitInfo};
+use super::metadata::{self, BadgeStyle, ReadmeConfig};
+use super::provider::{BadgeAttrs, BadgeContext, BadgeRegistry};
+use crate::suggest;
 
 /// Try to get manifest info from Cargo.toml
 pub fn get_manifest(project_root: &Path) -> Result<Manifest, String> {
+    get_manifest_with_badges(project_root, &BadgeRegistry::new())
+}
+
+/// Try to get manifest info from Cargo.toml, rendering `[badges]` with the providers of `registry`
+pub fn get_manifest_with_badges(
+    project_root: &Path,
+    registry: &BadgeRegistry,
+) -> Result<Manifest, String> {
+    let mut manifest = read_manifest(project_root)?;
+    let git = match manifest.badge_entries.is_empty() {
+        true => None,
+        false => git::git_info(project_root),
+    };
+    manifest.render_badges(registry, &BadgeStyle::default(), git.as_ref())?;
+    Ok(manifest)
+}
+
+/// Read the package and its settings from Cargo.toml, leaving its badges to be rendered
+///
+/// `generate_readme_with` renders them, with the badge style of the template.
+pub fn read_manifest(project_root: &Path) -> Result<Manifest, String> {
     let cargo_toml_path = project_root.join("Cargo.toml");
+    let text = std::fs::read_to_string(&cargo_toml_path)
+        .map_err(|e| format!("Could not read Cargo.toml: {}", e))?;
 
-    let manifest = cargo_toml::Manifest::<toml::Value>::from_path(&cargo_toml_path)
+    let mut manifest =
+        cargo_toml::Manifest::<toml::Value>::from_slice_with_metadata(text.as_bytes())
+            .map_err(|e| format!("Could not read Cargo.toml: {}", e))?;
+    manifest
+        .complete_from_path(&cargo_toml_path)
         .map_err(|e| format!("Could not read Cargo.toml: {}", e))?;
+    let raw_toml: RawBadges = toml::from_str(&text).map_err(|e| format!("{}", e))?;
+
+    let config = metadata::get_config(project_root, &manifest)?;
 
-    let raw_toml_text = std::fs::read_to_string(&cargo_toml_path)
+    if manifest.package.is_none() && manifest.workspace.is_some() {
+        return Ok(Manifest::from_virtual_workspace(
+            project_root,
+            manifest,
+            config,
+        ));
+    }
+
+    Manifest::try_new(manifest, raw_toml.badges, config)
+}
+
+/// Set `package.description` in Cargo.toml, keeping the rest of the file as it is
+pub fn set_description(project_root: &Path, description: &str) -> Result<(), String> {
+    let cargo_toml_path = project_root.join("Cargo.toml");
+    let text = std::fs::read_to_string(&cargo_toml_path)
         .map_err(|e| format!("Could not read Cargo.toml: {}", e))?;
-    let raw_toml: RawBadges = toml::from_str(&raw_toml_text).map_err(|e| format!("{}", e))?;
+    let mut document: toml_edit::DocumentMut = text
+        .parse()
+        .map_err(|e| format!("Could not parse Cargo.toml: {}", e))?;
 
-    Manifest::try_new(manifest, raw_toml.badges)
+    let package = document
+        .get_mut("package")
+        .and_then(|p| p.as_table_like_mut())
+        .ok_or_else(|| "Missing [package] section in Cargo.toml".to_string())?;
+
+    match package.get_mut("description") {
+        Some(item) => {
+            let value = item.as_value_mut().filter(|v| v.is_str()).ok_or_else(|| {
+                "Could not update `description`: it is not a plain string in Cargo.toml (is it \
+                 inherited from the workspace?)"
+                    .to_string()
+            })?;
+            // keep the comments and spacing around the old value
+            let decor = value.decor().clone();
+            *value = description.into();
+            *value.decor_mut() = decor;
+        }
+        None => {
+            package.insert("description", toml_edit::value(description));
+        }
+    }
+
+    std::fs::write(&cargo_toml_path, document.to_string())
+        .map_err(|e| format!("Could not write Cargo.toml: {}", e))
 }
 
 /// Error message for a field that could not be resolved through workspace inheritance
@@ -32,17 +106,29 @@ fn workspace_inherit_err(field: &str) -> String {
 #[derive(Debug)]
 pub struct Manifest {
     pub name: String,
+    pub description: Option<String>,
     pub license: Option<String>,
+    pub repository: Option<String>,
     pub lib: Option<ManifestLib>,
     pub bin: Vec<ManifestLib>,
-    pub badges: Vec<String>,
+    /// The rendered badges, empty until they are rendered.
+    pub badges: Vec<Badge>,
+    /// Mistakes in `[badges]` that did not prevent rendering, such as an unknown attribute.
+    pub badge_warnings: Vec<String>,
     pub version: String,
+    pub config: ReadmeConfig,
+    /// The project is the root of a virtual workspace, without a `[package]`.
+    pub virtual_workspace: bool,
+    /// The `[badges]` entries, one per badge to render.
+    pub(crate) badge_entries: Vec<(String, BadgeAttrs)>,
+    rust_version: Option<String>,
 }
 
 impl Manifest {
     fn try_new(
         manifest: cargo_toml::Manifest<toml::Value>,
-        badges_raw: Option<BTreeMap<String, BTreeMap<String, String>>>,
+        badges_raw: Option<BTreeMap<String, BadgeAttrs>>,
+        config: ReadmeConfig,
     ) -> Result<Manifest, String> {
         let package = manifest
             .package
@@ -51,6 +137,13 @@ impl Manifest {
 
         let name = package.name.clone();
 
+        let description = package
+            .description
+            .as_ref()
+            .map(|d| d.get().map(|s| s.to_owned()))
+            .transpose()
+            .map_err(|_| workspace_inherit_err("description"))?;
+
         let license = package
             .license
             .as_ref()
@@ -58,6 +151,13 @@ impl Manifest {
             .transpose()
             .map_err(|_| workspace_inherit_err("license"))?;
 
+        let repository = package
+            .repository
+            .as_ref()
+            .map(|r| r.get().map(|s| s.to_owned()))
+            .transpose()
+            .map_err(|_| workspace_inherit_err("repository"))?;
+
         let lib = manifest
             .lib
             .as_ref()
@@ -70,26 +170,133 @@ impl Manifest {
             .map(ManifestLib::from_product)
             .collect::<Result<Vec<_>, _>>()?;
 
-        let badges = badges_raw
-            .map(|b| process_badges(b, &name))
-            .transpose()?
-            .unwrap_or_default();
-
         let version = package
             .version
             .get()
             .map_err(|_| workspace_inherit_err("version"))?
             .to_string();
 
+        let rust_version = package
+            .rust_version
+            .as_ref()
+            .map(|r| r.get().map(|s| s.to_owned()))
+            .transpose()
+            .map_err(|_| workspace_inherit_err("rust-version"))?;
+
         Ok(Manifest {
             name,
+            description,
             license,
+            repository,
             lib,
             bin,
-            badges,
+            badges: Vec::new(),
+            badge_warnings: Vec::new(),
             version,
+            config,
+            virtual_workspace: false,
+            badge_entries: badges_raw.map(flatten_badges).unwrap_or_default(),
+            rust_version,
         })
     }
+
+    /// Render the badges with the providers of `registry`, replacing the ones rendered before
+    ///
+    /// The parameters of `style` take precedence over the `badge-style` table. `git` is the
+    /// repository the `[badges]` entries take the attributes they leave out from, along with
+    /// `package.repository`.
+    pub(crate) fn render_badges(
+        &mut self,
+        registry: &BadgeRegistry,
+        style: &BadgeStyle,
+        git: Option<&GitInfo>,
+    ) -> Result<(), String> {
+        // `[badges]` belongs to packages
+        if self.virtual_workspace {
+            return Ok(());
+        }
+
+        let style = style.clone().or(self.config.badge_style.clone());
+        self.badge_warnings = self
+            .badge_entries
+            .iter()
+            .flat_map(|(key, attrs)| badges::check_badge(registry, key, attrs))
+            .collect();
+        let defaults = badge_defaults(self.repository.as_deref(), git);
+        let context = BadgeContext {
+            name: &self.name,
+            version: &self.version,
+            repository: self.repository.as_deref(),
+            style: &style,
+        };
+        let mut badges = process_badges(self.badge_entries.clone(), &context, registry, &defaults)?;
+
+        if let Some(keys) = &self.config.auto_badges {
+            let package = AutoBadgeSource {
+                name: &self.name,
+                version: &self.version,
+                license: self.license.as_deref(),
+                rust_version: self.rust_version.as_deref(),
+            };
+            badges.extend(process_auto_badges(keys, &package, &style)?);
+        }
+
+        // the badges of other services come after the ones from `[badges]`
+        for badge in &self.config.badge {
+            badges.push(Badge {
+                key: "custom",
+                category: BadgeCategory::Custom,
+                markdown: badges::custom(
+                    badge,
+                    &self.name,
+                    &self.version,
+                    self.repository.as_deref(),
+                    &style,
+                )?,
+            });
+        }
+
+        // group the badges by category, keeping their order within each one
+        badges.sort_by_key(|badge| badge.category);
+        self.badges = badges;
+        Ok(())
+    }
+
+    /// Describe the root of a virtual workspace, which has no `[package]`
+    ///
+    /// The directory name stands for the crate name, and the version, description and license
+    /// come from `[workspace.package]` when it sets them. There is nothing to build, so no
+    /// targets, and no badges since `[badges]` belongs to packages.
+    fn from_virtual_workspace(
+        project_root: &Path,
+        manifest: cargo_toml::Manifest<toml::Value>,
+        config: ReadmeConfig,
+    ) -> Manifest {
+        let name = project_root
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let package = manifest
+            .workspace
+            .and_then(|w| w.package)
+            .unwrap_or_default();
+
+        Manifest {
+            name,
+            description: package.description,
+            license: package.license,
+            repository: package.repository,
+            lib: None,
+            bin: Vec::new(),
+            badges: Vec::new(),
+            badge_warnings: Vec::new(),
+            version: package.version.map(|v| v.to_string()).unwrap_or_default(),
+            config,
+            virtual_workspace: true,
+            badge_entries: Vec::new(),
+            rust_version: None,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -112,54 +319,163 @@ impl ManifestLib {
     }
 }
 
-// The keys matched here are the source of truth for which badges exist; they must stay
-// in sync with `badges::SUPPORTED_BADGES` (asserted by `supported_badges_in_sync`).
+/// What the `[badges]` entries of the package can leave out, read from `package.repository` and
+/// the local clone
+fn badge_defaults(repository: Option<&str>, git: Option<&GitInfo>) -> BadgeDefaults {
+    let clone = git.and_then(|git| {
+        let (_, slug) = forge::parse_repository_url(git.remote_url.as_deref()?)?;
+        Some((slug, git.default_branch.clone()?))
+    });
+
+    BadgeDefaults {
+        repository: repository.and_then(forge::parse_repository_url),
+        clone,
+    }
+}
+
+/// One entry per badge to render, repeating the `github` entry for each of its workflows
+///
+/// Cargo only accepts strings as badge attributes, so the workflows are listed in one
+/// `workflow-file`, separated by commas.
+fn flatten_badges(badges: BTreeMap<String, BadgeAttrs>) -> Vec<(String, BadgeAttrs)> {
+    let mut flat = Vec::new();
+    for (name, attrs) in badges {
+        let files = match attrs.get("workflow-file") {
+            Some(files) if name == "github" && files.contains(',') => files.clone(),
+            _ => {
+                flat.push((name, attrs));
+                continue;
+            }
+        };
+        for file in files.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+            let mut attrs = attrs.clone();
+            attrs.insert("workflow-file".to_owned(), file.to_owned());
+            flat.push((name.clone(), attrs));
+        }
+    }
+    flat
+}
+
+/// Render the `[badges]` entries with the providers of `registry`
+///
+/// `package` is the context of every badge, whose style is completed by the style attributes of
+/// each entry. Keys without a provider are left out, `badges::check_badge` reports them.
 fn process_badges(
-    badges: BTreeMap<String, BTreeMap<String, String>>,
-    crate_name: &str,
-) -> Result<Vec<String>, String> {
-    let mut b: Vec<(u16, String)> = badges
-        .into_iter()
-        .filter_map(|(name, attrs)| match name.as_ref() {
-            "crates-io" => Some((0, badges::crates_io(attrs, crate_name))),
-            "appveyor" => Some((1, badges::appveyor(attrs))),
-            "circle-ci" => Some((2, badges::circle_ci(attrs))),
-            "gitlab" => Some((3, badges::gitlab(attrs))),
-            "travis-ci" => Some((4, badges::travis_ci(attrs))),
-            "github" => Some((5, badges::github(attrs))),
-            "codecov" => Some((6, badges::codecov(attrs))),
-            "coveralls" => Some((7, badges::coveralls(attrs))),
-            "is-it-maintained-issue-resolution" => {
-                Some((8, badges::is_it_maintained_issue_resolution(attrs)))
+    badges: Vec<(String, BadgeAttrs)>,
+    package: &BadgeContext,
+    registry: &BadgeRegistry,
+    defaults: &BadgeDefaults,
+) -> Result<Vec<Badge>, String> {
+    let mut b: Vec<(usize, Badge)> = Vec::new();
+    for (key, mut attrs) in badges {
+        let Some((order, provider)) = registry.get(&key) else {
+            continue;
+        };
+
+        let info = provider.info();
+        defaults.apply(info, &mut attrs);
+        // the style attributes of the badge take precedence over the `badge-style` table
+        let style = badges::style_from_attrs(&attrs).or(package.style.clone());
+        badges::check_style(&style)?;
+        let context = BadgeContext {
+            style: &style,
+            ..*package
+        };
+
+        let badge = Badge {
+            key: info.key,
+            category: info.category,
+            markdown: provider.render(attrs, &context)?,
+        };
+        b.push((order, badge));
+    }
+
+    // the order is the provider's place in the registry; repeated badges keep the order they
+    // are listed in
+    b.sort_by_key(|(order, _)| *order);
+    Ok(b.into_iter().map(|(_, badge)| badge).collect())
+}
+
+/// The package metadata the automatic badges are derived from
+struct AutoBadgeSource<'a> {
+    name: &'a str,
+    version: &'a str,
+    license: Option<&'a str>,
+    rust_version: Option<&'a str>,
+}
+
+// The keys matched here must stay in sync with `badges::AUTO_BADGES`, in the same order
+// (asserted by `auto_badges_in_sync`).
+fn process_auto_badges(
+    keys: &[String],
+    package: &AutoBadgeSource,
+    style: &BadgeStyle,
+) -> Result<Vec<Badge>, String> {
+    badges::check_style(style)?;
+
+    let mut b: Vec<(usize, String)> = Vec::new();
+    for key in keys {
+        let (order, markdown) = match key.as_str() {
+            "docs-rs" => (0, Some(badges::docs_rs(package.name, style))),
+            "downloads" => (1, Some(badges::downloads(package.name, style))),
+            "license" => (2, package.license.map(|l| badges::license(l, style))),
+            "msrv" => (3, package.rust_version.map(|r| badges::msrv(r, style))),
+            "deps-rs" => (
+                4,
+                Some(badges::deps_rs(package.name, package.version, style)),
+            ),
+            _ => {
+                let known = badges::AUTO_BADGES.iter().map(|b| b.key);
+                let mut msg = format!("Unknown badge `{key}` in `auto-badges`");
+                if let Some(known) = suggest::did_you_mean(key, known) {
+                    msg.push_str(&format!(" (did you mean `{known}`?)"));
+                }
+                return Err(msg);
             }
-            "is-it-maintained-open-issues" => {
-                Some((9, badges::is_it_maintained_open_issues(attrs)))
+        };
+
+        let info = &badges::AUTO_BADGES[order];
+        match markdown {
+            Some(markdown) if !b.iter().any(|(o, _)| *o == order) => b.push((order, markdown)),
+            Some(_) => {}
+            None => eprintln!(
+                "Warn: the `{}` badge needs `{}` in Cargo.toml",
+                key, info.source
+            ),
+        }
+    }
+
+    b.sort_by_key(|a| a.0);
+    Ok(b.into_iter()
+        .map(|(order, markdown)| {
+            let info = &badges::AUTO_BADGES[order];
+            Badge {
+                key: info.key,
+                category: info.category,
+                markdown,
             }
-            "maintenance" => Some((10, badges::maintenance(attrs))),
-            _ => None,
         })
-        .map(|(order, badge)| badge.map(|b| (order, b)))
-        .collect::<Result<_, _>>()?;
-
-    b.sort_unstable_by_key(|a| a.0);
-    Ok(b.into_iter().map(|(_, badge)| badge).collect())
+        .collect())
 }
 
 /// Raw badges extraction from TOML
 #[derive(Clone, Deserialize)]
 struct RawBadges {
-    pub badges: Option<BTreeMap<String, BTreeMap<String, String>>>,
+    pub badges: Option<BTreeMap<String, BadgeAttrs>>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    // Guards against `SUPPORTED_BADGES` (used by --list-badges) drifting from the badge
-    // keys `process_badges` actually renders.
+    // Guards against `SUPPORTED_BADGES` (used by --list-badges) drifting from the badges the
+    // built-in providers render.
     #[test]
     fn supported_badges_in_sync() {
-        let documented: Vec<&str> = badges::SUPPORTED_BADGES.iter().map(|b| b.key).collect();
+        let documented: Vec<&str> = badges::SUPPORTED_BADGES
+            .iter()
+            .map(|b| b.info.key)
+            .collect();
         for key in [
             "crates-io",
             "appveyor",
@@ -167,6 +483,12 @@ mod tests {
             "gitlab",
             "travis-ci",
             "github",
+            "azure-devops",
+            "bitbucket-pipelines",
+            "cirrus-ci",
+            "gitea",
+            "forgejo",
+            "woodpecker",
             "codecov",
             "coveralls",
             "is-it-maintained-issue-resolution",
@@ -176,11 +498,32 @@ mod tests {
             let mut attrs = BTreeMap::new();
             attrs.insert("repository".to_string(), "owner/repo".to_string());
             attrs.insert("status".to_string(), "actively-developed".to_string());
-            let mut input = BTreeMap::new();
-            input.insert(key.to_string(), attrs);
+            attrs.insert("project".to_string(), "org/project".to_string());
+            attrs.insert("pipeline".to_string(), "ci".to_string());
+            attrs.insert("branch".to_string(), "main".to_string());
+            attrs.insert("workflow-file".to_string(), "ci.yml".to_string());
+            attrs.insert("host".to_string(), "git.example.com".to_string());
+            let input = vec![(key.to_string(), attrs)];
 
-            let rendered = process_badges(input, "some-crate").unwrap();
+            let style = BadgeStyle::default();
+            let context = BadgeContext {
+                name: "some-crate",
+                version: "1.0.0",
+                repository: None,
+                style: &style,
+            };
+            let rendered = process_badges(
+                input,
+                &context,
+                &BadgeRegistry::new(),
+                &BadgeDefaults::default(),
+            )
+            .unwrap();
             assert_eq!(rendered.len(), 1, "`{key}` should render a badge");
+            assert_eq!(
+                rendered[0].key, key,
+                "`{key}` is out of order in SUPPORTED_BADGES"
+            );
             assert!(
                 documented.contains(&key),
                 "`{key}` missing from SUPPORTED_BADGES"
@@ -189,8 +532,66 @@ mod tests {
 
         assert_eq!(
             documented.len(),
-            11,
+            17,
             "SUPPORTED_BADGES has an unexpected count"
         );
     }
+
+    #[test]
+    fn github_badge_per_workflow_file() {
+        let attrs = |file: &str| {
+            BTreeMap::from([
+                ("repository".to_owned(), "owner/repo".to_owned()),
+                ("workflow-file".to_owned(), file.to_owned()),
+            ])
+        };
+        let input = BTreeMap::from([
+            ("github".to_owned(), attrs("ci.yml, release.yml")),
+            ("gitea".to_owned(), attrs("ci.yml, release.yml")),
+        ]);
+        assert_eq!(
+            vec![
+                ("gitea".to_owned(), attrs("ci.yml, release.yml")),
+                ("github".to_owned(), attrs("ci.yml")),
+                ("github".to_owned(), attrs("release.yml")),
+            ],
+            flatten_badges(input)
+        );
+    }
+
+    // Guards against `AUTO_BADGES` drifting from the keys `process_auto_badges` renders.
+    #[test]
+    fn auto_badges_in_sync() {
+        let package = AutoBadgeSource {
+            name: "some-crate",
+            version: "1.0.0",
+            license: Some("MIT"),
+            rust_version: Some("1.80"),
+        };
+        let keys: Vec<String> = badges::AUTO_BADGES
+            .iter()
+            .rev()
+            .map(|b| b.key.to_owned())
+            .collect();
+
+        let rendered = process_auto_badges(&keys, &package, &BadgeStyle::default()).unwrap();
+        let rendered: Vec<&str> = rendered.iter().map(|b| b.key).collect();
+        let documented: Vec<&str> = badges::AUTO_BADGES.iter().map(|b| b.key).collect();
+        assert_eq!(documented, rendered);
+    }
+
+    #[test]
+    fn unknown_auto_badge_should_fail() {
+        let package = AutoBadgeSource {
+            name: "some-crate",
+            version: "1.0.0",
+            license: None,
+            rust_version: None,
+        };
+        assert_eq!(
+            "Unknown badge `doc-rs` in `auto-badges` (did you mean `docs-rs`?)",
+            process_auto_badges(&["doc-rs".to_owned()], &package, &BadgeStyle::default())
+                .unwrap_err()
+        );
+    }
 }
@@ -0,0 +1,287 @@
+//! Read cargo-readme settings from `[package.metadata.readme]` and `[workspace.metadata.readme]`
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::project;
+
+/// Defaults for the command line options, read from the manifest
+///
+/// Keys are named after the command line flags, e.g. `no-badges = true` or
+/// `template = "README.member.tpl"`. Paths are relative to the project root. Settings from
+/// `[package.metadata.readme]` take precedence over `[workspace.metadata.readme]`, and flags given
+/// on the command line take precedence over both. The `vars` tables of both are merged, and the
+/// `badge` entries of the workspace come before the package's.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ReadmeConfig {
+    pub no_badges: Option<bool>,
+    pub no_indent_headings: Option<bool>,
+    pub no_license: Option<bool>,
+    pub license_section: Option<bool>,
+    pub changelog_entries: Option<usize>,
+    pub no_template: Option<bool>,
+    pub no_title: Option<bool>,
+    pub no_comment_extraction: Option<bool>,
+    pub strict: Option<bool>,
+    pub title: Option<String>,
+    pub subtitle: Option<bool>,
+    pub docs_link: Option<bool>,
+    pub install_section: Option<bool>,
+    pub centered_header: Option<bool>,
+    /// Badges to derive from the package metadata, by key, such as `"docs-rs"`.
+    pub auto_badges: Option<Vec<String>>,
+    /// How shields.io badges look, unless a badge sets its own style.
+    #[serde(default)]
+    pub badge_style: BadgeStyle,
+    /// How the badges are placed: `lines`, `inline`, `html` or `reference`.
+    pub badge_layout: Option<String>,
+    pub input: Option<String>,
+    pub output: Option<String>,
+    pub template: Option<String>,
+    /// User defined template variables, from the `vars` sub-table.
+    #[serde(default)]
+    pub vars: BTreeMap<String, String>,
+    /// Badges for services cargo-readme does not know, from the `badge` array of tables.
+    #[serde(default)]
+    pub badge: Vec<CustomBadge>,
+}
+
+/// A badge defined by a `[[package.metadata.readme.badge]]` entry
+///
+/// The fields can refer to the package as `{name}`, `{version}` and `{repository}`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomBadge {
+    /// Alternative text of the image.
+    pub alt: String,
+    /// URL of the badge image.
+    pub image: String,
+    /// Where the badge links to, if anywhere.
+    pub link: Option<String>,
+    /// Tooltip of the image.
+    pub title: Option<String>,
+    pub style: Option<String>,
+    pub logo: Option<String>,
+    pub label: Option<String>,
+    pub color: Option<String>,
+}
+
+impl CustomBadge {
+    /// The style set on the badge itself
+    pub fn style(&self) -> BadgeStyle {
+        BadgeStyle {
+            style: self.style.clone(),
+            logo: self.logo.clone(),
+            label: self.label.clone(),
+            color: self.color.clone(),
+        }
+    }
+}
+
+/// The shields.io parameters of a badge, from the `badge-style` table or from the badge itself
+///
+/// Only badges whose image is served by shields.io are styled.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BadgeStyle {
+    /// One of `flat`, `flat-square`, `plastic`, `for-the-badge` or `social`.
+    pub style: Option<String>,
+    /// Name of a simple-icons logo, such as `rust`.
+    pub logo: Option<String>,
+    /// Text of the left-hand side of the badge.
+    pub label: Option<String>,
+    /// Color of the right-hand side of the badge, a name or a hex code.
+    pub color: Option<String>,
+}
+
+impl BadgeStyle {
+    /// Fill the parameters missing here from `fallback`
+    pub fn or(self, fallback: BadgeStyle) -> BadgeStyle {
+        BadgeStyle {
+            style: self.style.or(fallback.style),
+            logo: self.logo.or(fallback.logo),
+            label: self.label.or(fallback.label),
+            color: self.color.or(fallback.color),
+        }
+    }
+}
+
+impl ReadmeConfig {
+    /// Fill the settings missing here from `fallback`
+    pub fn or(self, fallback: ReadmeConfig) -> ReadmeConfig {
+        let mut vars = fallback.vars;
+        vars.extend(self.vars);
+        let mut badge = fallback.badge;
+        badge.extend(self.badge);
+
+        ReadmeConfig {
+            no_badges: self.no_badges.or(fallback.no_badges),
+            no_indent_headings: self.no_indent_headings.or(fallback.no_indent_headings),
+            no_license: self.no_license.or(fallback.no_license),
+            license_section: self.license_section.or(fallback.license_section),
+            changelog_entries: self.changelog_entries.or(fallback.changelog_entries),
+            no_template: self.no_template.or(fallback.no_template),
+            no_title: self.no_title.or(fallback.no_title),
+            no_comment_extraction: self
+                .no_comment_extraction
+                .or(fallback.no_comment_extraction),
+            strict: self.strict.or(fallback.strict),
+            title: self.title.or(fallback.title),
+            subtitle: self.subtitle.or(fallback.subtitle),
+            docs_link: self.docs_link.or(fallback.docs_link),
+            install_section: self.install_section.or(fallback.install_section),
+            centered_header: self.centered_header.or(fallback.centered_header),
+            badge_layout: self.badge_layout.or(fallback.badge_layout),
+            auto_badges: self.auto_badges.or(fallback.auto_badges),
+            badge_style: self.badge_style.or(fallback.badge_style),
+            input: self.input.or(fallback.input),
+            output: self.output.or(fallback.output),
+            template: self.template.or(fallback.template),
+            vars,
+            badge,
+        }
+    }
+}
+
+/// Read the settings of the project, layering the package's over the workspace's
+pub fn get_config(
+    project_root: &Path,
+    manifest: &cargo_toml::Manifest<toml::Value>,
+) -> Result<ReadmeConfig, String> {
+    let package = manifest
+        .package
+        .as_ref()
+        .and_then(|p| p.metadata.as_ref())
+        .map(|metadata| from_metadata(metadata, "package"))
+        .transpose()?
+        .unwrap_or_default();
+
+    let explicit_root = manifest
+        .package
+        .as_ref()
+        .and_then(|p| p.workspace.as_deref());
+    let workspace = match project::find_workspace_root(project_root, explicit_root) {
+        Some(workspace_root) => {
+            let workspace_toml = project::read_toml(&workspace_root.join("Cargo.toml"))?;
+            workspace_toml
+                .get("workspace")
+                .and_then(|w| w.get("metadata"))
+                .map(|metadata| from_metadata(metadata, "workspace"))
+                .transpose()?
+                .unwrap_or_default()
+        }
+        None => ReadmeConfig::default(),
+    };
+
+    Ok(package.or(workspace))
+}
+
+/// Deserialize the `readme` table of a `[package.metadata]` or `[workspace.metadata]` table
+fn from_metadata(metadata: &toml::Value, section: &str) -> Result<ReadmeConfig, String> {
+    match metadata.get("readme") {
+        Some(readme) => readme.clone().try_into().map_err(|e: toml::de::Error| {
+            // the error names the offending key on a second line
+            let e = e.to_string().trim().replace('\n', " ");
+            format!("Invalid `[{section}.metadata.readme]` in Cargo.toml: {e}")
+        }),
+        None => Ok(ReadmeConfig::default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(toml: &str) -> toml::Value {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn read_readme_table() {
+        let config = from_metadata(
+            &metadata("[readme]\nno-badges = true\ntemplate = \"OTHER.tpl\""),
+            "package",
+        )
+        .unwrap();
+        assert_eq!(Some(true), config.no_badges);
+        assert_eq!(Some("OTHER.tpl"), config.template.as_deref());
+        assert_eq!(None, config.no_license);
+    }
+
+    #[test]
+    fn unknown_key_should_fail() {
+        let err = from_metadata(&metadata("[readme]\nno-badgs = true"), "package").unwrap_err();
+        assert!(
+            err.starts_with(
+                "Invalid `[package.metadata.readme]` in Cargo.toml: unknown field `no-badgs`"
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn invalid_type_should_name_key() {
+        let err = from_metadata(&metadata("[readme]\nstrict = \"yes\""), "workspace").unwrap_err();
+        assert_eq!(
+            "Invalid `[workspace.metadata.readme]` in Cargo.toml: invalid type: string \"yes\", \
+             expected a boolean in `strict`",
+            err
+        );
+    }
+
+    #[test]
+    fn read_vars_table() {
+        let config = from_metadata(
+            &metadata("[readme.vars]\nsupport_channel = \"#help\""),
+            "package",
+        )
+        .unwrap();
+        assert_eq!("#help", config.vars["support_channel"]);
+    }
+
+    #[test]
+    fn read_badge_entries() {
+        let config = from_metadata(
+            &metadata(
+                "[[readme.badge]]\nalt = \"deps\"\nimage = \"https://deps.rs/crate/{name}/{version}/status.svg\"",
+            ),
+            "package",
+        )
+        .unwrap();
+        assert_eq!("deps", config.badge[0].alt);
+        assert_eq!(None, config.badge[0].link);
+
+        let err = from_metadata(
+            &metadata("[[readme.badge]]\nalt = \"deps\"\nimage = \"x\"\nurl = \"y\""),
+            "package",
+        )
+        .unwrap_err();
+        assert!(err.contains("unknown field `url`"), "{err}");
+    }
+
+    #[test]
+    fn package_overrides_workspace() {
+        let package = ReadmeConfig {
+            no_badges: Some(false),
+            vars: [("a".to_owned(), "package".to_owned())].into(),
+            ..Default::default()
+        };
+        let workspace = ReadmeConfig {
+            no_badges: Some(true),
+            no_license: Some(true),
+            vars: [
+                ("a".to_owned(), "workspace".to_owned()),
+                ("b".to_owned(), "workspace".to_owned()),
+            ]
+            .into(),
+            ..Default::default()
+        };
+        let config = package.or(workspace);
+        assert_eq!(Some(false), config.no_badges);
+        assert_eq!(Some(true), config.no_license);
+        assert_eq!("package", config.vars["a"]);
+        assert_eq!("workspace", config.vars["b"]);
+    }
+}
@@ -1,12 +1,26 @@
 mod badges;
+mod forge;
+mod git;
 mod manifest;
+mod metadata;
 pub mod project;
+mod provider;
 
-pub use self::badges::{BadgeInfo, SUPPORTED_BADGES};
-pub use self::manifest::get_manifest;
+pub use self::badges::{
+    AutoBadgeInfo, Badge, BadgeCategory, BadgeInfo, AUTO_BADGES, SUPPORTED_BADGES,
+};
+pub use self::git::{git_info, GitInfo};
 pub use self::manifest::Manifest;
+pub use self::manifest::{get_manifest, get_manifest_with_badges, read_manifest, set_description};
+pub use self::metadata::{BadgeStyle, CustomBadge, ReadmeConfig};
+pub use self::provider::{BadgeAttrs, BadgeContext, BadgeProvider, BadgeRegistry};
 
 /// The badges cargo-readme can render, in output order.
-pub fn supported_badges() -> &'static [BadgeInfo] {
-    SUPPORTED_BADGES
+pub fn supported_badges() -> impl Iterator<Item = &'static BadgeInfo> {
+    SUPPORTED_BADGES.iter().map(|badge| &badge.info)
+}
+
+/// The badges cargo-readme can derive from the package metadata, in output order.
+pub fn auto_badges() -> &'static [AutoBadgeInfo] {
+    AUTO_BADGES
 }
@@ -32,6 +32,149 @@ pub fn get_root(given_root: Option<&str>) -> Result<PathBuf, String> {
     Ok(root)
 }
 
+/// Find the root of the workspace the project belongs to, if any
+///
+/// Follows `package.workspace` when it is set, otherwise walks up from the project root to the
+/// first `Cargo.toml` with a `[workspace]` table, like cargo does. A project that is itself the
+/// workspace root is its own workspace root.
+pub fn find_workspace_root(project_root: &Path, explicit_root: Option<&Path>) -> Option<PathBuf> {
+    if let Some(explicit_root) = explicit_root {
+        let root = project_root.join(explicit_root);
+        return root.join("Cargo.toml").is_file().then_some(root);
+    }
+
+    project_root
+        .ancestors()
+        .filter(|dir| dir.join("Cargo.toml").is_file())
+        .find(|dir| {
+            read_toml(&dir.join("Cargo.toml"))
+                .map(|toml| toml.get("workspace").is_some())
+                .unwrap_or(false)
+        })
+        .map(Path::to_path_buf)
+}
+
+/// Is the project the root of a virtual workspace, a `Cargo.toml` with `[workspace]` but no
+/// `[package]`?
+pub fn is_virtual_workspace(project_root: &Path) -> bool {
+    read_toml(&project_root.join("Cargo.toml"))
+        .map(|toml| toml.get("workspace").is_some() && toml.get("package").is_none())
+        .unwrap_or(false)
+}
+
+/// Find the directories of the members of the workspace rooted at `workspace_root`
+///
+/// The root package comes first when there is one, then the entries of `workspace.members` in
+/// the order they are listed, without the ones in `workspace.exclude`. Entries can use `*` and
+/// `?` wildcards in their path components, like cargo; the directories they match are sorted.
+pub fn workspace_members(workspace_root: &Path) -> Result<Vec<PathBuf>, String> {
+    let manifest_path = workspace_root.join("Cargo.toml");
+    let toml = read_toml(&manifest_path)?;
+    let workspace = toml.get("workspace").ok_or_else(|| {
+        format!(
+            "Missing [workspace] section in {}",
+            manifest_path.to_string_lossy()
+        )
+    })?;
+
+    let patterns = |key: &str| -> Vec<String> {
+        workspace
+            .get(key)
+            .and_then(toml::Value::as_array)
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(toml::Value::as_str)
+                    .map(str::to_owned)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let excluded: Vec<PathBuf> = patterns("exclude")
+        .iter()
+        .map(|path| workspace_root.join(path))
+        .collect();
+
+    let mut members = Vec::new();
+    if toml.get("package").is_some() {
+        members.push(workspace_root.to_path_buf());
+    }
+    for pattern in patterns("members") {
+        for member in expand_member_pattern(workspace_root, &pattern)? {
+            if !members.contains(&member) && !excluded.iter().any(|e| member.starts_with(e)) {
+                members.push(member);
+            }
+        }
+    }
+
+    Ok(members)
+}
+
+/// The member directories matched by an entry of `workspace.members`
+fn expand_member_pattern(workspace_root: &Path, pattern: &str) -> Result<Vec<PathBuf>, String> {
+    let mut dirs = vec![workspace_root.to_path_buf()];
+    for component in pattern.split('/').filter(|c| !c.is_empty() && *c != ".") {
+        if !component.contains(['*', '?']) {
+            dirs = dirs.into_iter().map(|dir| dir.join(component)).collect();
+            continue;
+        }
+
+        let mut matches = Vec::new();
+        for dir in dirs.iter().filter(|dir| dir.is_dir()) {
+            let entries = std::fs::read_dir(dir)
+                .map_err(|e| format!("Could not read {}: {}", dir.to_string_lossy(), e))?;
+            for entry in entries.flatten() {
+                let name = entry.file_name();
+                if entry.path().is_dir() && wildcard_match(component, &name.to_string_lossy()) {
+                    matches.push(entry.path());
+                }
+            }
+        }
+        matches.sort();
+        dirs = matches;
+    }
+
+    let (members, missing): (Vec<_>, Vec<_>) = dirs
+        .into_iter()
+        .partition(|dir| dir.join("Cargo.toml").is_file());
+    // only literal paths are expected to be crates, wildcards also match other directories
+    if let Some(dir) = missing.first().filter(|_| !pattern.contains(['*', '?'])) {
+        return Err(format!(
+            "Workspace member `{}` has no Cargo.toml in {}",
+            pattern,
+            dir.to_string_lossy()
+        ));
+    }
+
+    Ok(members)
+}
+
+/// Match a file name against a pattern where `*` stands for any run of characters and `?` for
+/// any single character
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    fn matches(pattern: &[char], name: &[char]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some(('*', rest)) => (0..=name.len()).any(|skip| matches(rest, &name[skip..])),
+            Some((p, rest)) => match name.split_first() {
+                Some((n, name)) => (*p == '?' || p == n) && matches(rest, name),
+                None => false,
+            },
+        }
+    }
+
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    matches(&pattern, &name)
+}
+
+/// Parse a TOML file without interpreting it as a manifest
+pub(crate) fn read_toml(path: &Path) -> Result<toml::Value, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("Could not read {}: {}", path.to_string_lossy(), e))?;
+    toml::from_str(&text).map_err(|e| format!("Could not parse {}: {}", path.to_string_lossy(), e))
+}
+
 /// Find the default entrypoiny to read the doc comments from
 ///
 /// Try to read entrypoint in the following order:
@@ -88,3 +231,17 @@ pub fn find_entrypoint(current_dir: &Path, manifest: &Manifest) -> Result<PathBu
     // if no entrypoint is found, return an error
     Err("No entrypoint found".to_owned())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcard_match_components() {
+        assert!(wildcard_match("*", "core"));
+        assert!(wildcard_match("cargo-*", "cargo-readme"));
+        assert!(wildcard_match("v?", "v2"));
+        assert!(!wildcard_match("cargo-*", "readme"));
+        assert!(!wildcard_match("v?", "v10"));
+    }
+}
@@ -0,0 +1,186 @@
+//! Badge providers
+//!
+//! Each `[badges]` key is rendered by a [`BadgeProvider`] looked up in a [`BadgeRegistry`].
+//! cargo-readme's own badges are providers too, so a library user can add badges for other
+//! services, or replace the built-in ones, and have `[badges]` entries rendered and checked the
+//! same way.
+
+use std::collections::BTreeMap;
+
+use super::badges::{BadgeInfo, BuiltinBadge, SUPPORTED_BADGES};
+use super::metadata::BadgeStyle;
+
+/// The attributes of a `[badges]` entry
+pub type BadgeAttrs = BTreeMap<String, String>;
+
+/// What a badge is rendered from, besides the attributes of its `[badges]` entry
+#[derive(Debug, Clone, Copy)]
+pub struct BadgeContext<'a> {
+    /// `package.name`.
+    pub name: &'a str,
+    /// `package.version`.
+    pub version: &'a str,
+    /// `package.repository`, if set.
+    pub repository: Option<&'a str>,
+    /// The shields.io parameters of the badge, its own attributes over the `badge-style` table.
+    pub style: &'a BadgeStyle,
+}
+
+/// Renders the badge of a `[badges]` key
+pub trait BadgeProvider {
+    /// The key of the badge and the attributes it reads, used to check the `[badges]` entries
+    /// and listed by `--list-badges`.
+    fn info(&self) -> &BadgeInfo;
+
+    /// The markdown of the badge for the attributes of a `[badges]` entry
+    ///
+    /// A missing required attribute is an error. The attributes left out have already been
+    /// filled from `package.repository` where they can be.
+    fn render(&self, attrs: BadgeAttrs, context: &BadgeContext) -> Result<String, String>;
+}
+
+/// The badge providers, in the order their badges appear in the output
+pub struct BadgeRegistry {
+    providers: Vec<Box<dyn BadgeProvider>>,
+}
+
+impl BadgeRegistry {
+    /// A registry of the badges cargo-readme renders on its own, in the order of `supported_badges`
+    pub fn new() -> BadgeRegistry {
+        let providers = SUPPORTED_BADGES
+            .iter()
+            .map(|badge| Box::new(Builtin(badge)) as Box<dyn BadgeProvider>)
+            .collect();
+        BadgeRegistry { providers }
+    }
+
+    /// Add a provider
+    ///
+    /// A provider for the key of one already registered takes its place, otherwise its badges
+    /// come after the ones of the providers registered before.
+    pub fn register(&mut self, provider: impl BadgeProvider + 'static) {
+        let key = provider.info().key;
+        match self.position(key) {
+            Some(index) => self.providers[index] = Box::new(provider),
+            None => self.providers.push(Box::new(provider)),
+        }
+    }
+
+    /// The provider of the badge under `key`, with its place in the output
+    pub fn get(&self, key: &str) -> Option<(usize, &dyn BadgeProvider)> {
+        self.position(key)
+            .map(|index| (index, self.providers[index].as_ref()))
+    }
+
+    /// Every provider, in output order
+    pub fn providers(&self) -> impl Iterator<Item = &dyn BadgeProvider> {
+        self.providers.iter().map(|provider| provider.as_ref())
+    }
+
+    fn position(&self, key: &str) -> Option<usize> {
+        self.providers
+            .iter()
+            .position(|provider| provider.info().key == key)
+    }
+}
+
+impl Default for BadgeRegistry {
+    fn default() -> Self {
+        BadgeRegistry::new()
+    }
+}
+
+/// A badge cargo-readme renders on its own
+struct Builtin(&'static BuiltinBadge);
+
+impl BadgeProvider for Builtin {
+    fn info(&self) -> &BadgeInfo {
+        &self.0.info
+    }
+
+    fn render(&self, attrs: BadgeAttrs, context: &BadgeContext) -> Result<String, String> {
+        (self.0.render)(attrs, context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BadgeCategory;
+
+    struct Chat;
+
+    const CHAT: BadgeInfo = BadgeInfo {
+        key: "chat",
+        official: false,
+        category: BadgeCategory::Custom,
+        required: &["room"],
+        optional: &[],
+        values: &[],
+    };
+
+    impl BadgeProvider for Chat {
+        fn info(&self) -> &BadgeInfo {
+            &CHAT
+        }
+
+        fn render(&self, attrs: BadgeAttrs, context: &BadgeContext) -> Result<String, String> {
+            let room = attrs.get("room").ok_or("missing `room`")?;
+            Ok(format!(
+                "![chat about {}](https://chat.example/{room}.svg)",
+                context.name
+            ))
+        }
+    }
+
+    #[test]
+    fn registered_providers_come_last() {
+        let mut registry = BadgeRegistry::new();
+        registry.register(Chat);
+
+        let (index, provider) = registry.get("chat").unwrap();
+        assert_eq!(SUPPORTED_BADGES.len(), index);
+
+        let style = BadgeStyle::default();
+        let context = BadgeContext {
+            name: "some-crate",
+            version: "1.0.0",
+            repository: None,
+            style: &style,
+        };
+        let attrs = BadgeAttrs::from([("room".to_owned(), "general".to_owned())]);
+        assert_eq!(
+            "![chat about some-crate](https://chat.example/general.svg)",
+            provider.render(attrs, &context).unwrap()
+        );
+    }
+
+    #[test]
+    fn provider_for_known_key_takes_its_place() {
+        struct Maintenance;
+        const MAINTENANCE: BadgeInfo = BadgeInfo {
+            key: "maintenance",
+            ..CHAT
+        };
+        impl BadgeProvider for Maintenance {
+            fn info(&self) -> &BadgeInfo {
+                &MAINTENANCE
+            }
+
+            fn render(&self, _: BadgeAttrs, _: &BadgeContext) -> Result<String, String> {
+                Ok("maintained".to_owned())
+            }
+        }
+
+        let mut registry = BadgeRegistry::new();
+        let (index, _) = registry.get("maintenance").unwrap();
+        registry.register(Maintenance);
+
+        assert_eq!(SUPPORTED_BADGES.len(), registry.providers().count());
+        assert_eq!(Some(index), registry.get("maintenance").map(|(i, _)| i));
+        assert_eq!(
+            &["room"],
+            registry.get("maintenance").unwrap().1.info().required
+        );
+    }
+}
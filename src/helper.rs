@@ -1,11 +1,13 @@
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{ErrorKind, Write};
 use std::path::{Path, PathBuf};
 
-use cargo_readme::get_manifest;
 use cargo_readme::project;
+use cargo_readme::Manifest;
 
 const DEFAULT_TEMPLATE: &str = "README.tpl";
+const BUILTIN_PREFIX: &str = "builtin:";
 
 /// Get the project root from given path or defaults to current directory
 ///
@@ -17,14 +19,18 @@ pub fn get_project_root(given_root: Option<&str>) -> Result<PathBuf, String> {
 }
 
 /// Get the source file from which the doc comments will be extracted
-pub fn get_source(project_root: &Path, input: Option<&str>) -> Result<File, String> {
+pub fn get_source(
+    project_root: &Path,
+    input: Option<&str>,
+    manifest: &Manifest,
+) -> Result<File, String> {
     match input {
         Some(input) => {
             let input = project_root.join(input);
             File::open(&input)
                 .map_err(|e| format!("Could not open file '{}': {}", input.to_string_lossy(), e))
         }
-        None => find_entrypoint(project_root),
+        None => find_entrypoint(project_root, manifest),
     }
 }
 
@@ -38,40 +44,137 @@ pub fn get_dest(output: Option<&str>) -> Result<Option<File>, String> {
     }
 }
 
-/// Get the template file that will be used to render the output
-pub fn get_template_file(
-    project_root: &Path,
-    template: Option<&str>,
-) -> Result<Option<File>, String> {
+/// Get the template that will be used to render the output
+///
+/// A template named `builtin:NAME` is one of the templates shipped inside the binary, anything
+/// else is a path relative to the project root. Without a template, `README.tpl` is looked up in
+/// the project root, then in each directory up to the workspace root. Templates read from a file
+/// are returned with their `{{extends}}` chain resolved.
+pub fn get_template(project_root: &Path, template: Option<&str>) -> Result<Option<String>, String> {
     match template {
+        // builtin template was given, read it from the binary
+        Some(template) if template.starts_with(BUILTIN_PREFIX) => {
+            get_builtin_template(template).map(|content| Some(content.to_owned()))
+        }
         // template path was given, try to read it
         Some(template) => {
             let template = project_root.join(template);
-            File::open(&template).map(Some).map_err(|e| {
+            let content = std::fs::read_to_string(&template).map_err(|e| {
                 format!(
                     "Could not open template file '{}': {}",
                     template.to_string_lossy(),
                     e
                 )
-            })
+            })?;
+            resolve_template_file(&template, &content).map(Some)
         }
-        // try to read the default template file
+        // try to read the default template file, in the project or the workspace around it
         None => {
-            let template = project_root.join(DEFAULT_TEMPLATE);
-            match File::open(&template) {
-                Ok(file) => Ok(Some(file)),
-                // do not generate an error on file not found
-                Err(ref e) if e.kind() != ErrorKind::NotFound => Err(format!(
-                    "Could not open template file '{}': {}",
-                    DEFAULT_TEMPLATE, e
-                )),
-                // default template not found, return `None`
-                _ => Ok(None),
+            for dir in default_template_dirs(project_root) {
+                let template = dir.join(DEFAULT_TEMPLATE);
+                match std::fs::read_to_string(&template) {
+                    Ok(content) => return resolve_template_file(&template, &content).map(Some),
+                    // do not generate an error on file not found
+                    Err(ref e) if e.kind() != ErrorKind::NotFound => {
+                        return Err(format!(
+                            "Could not open template file '{}': {}",
+                            template.to_string_lossy(),
+                            e
+                        ))
+                    }
+                    _ => {}
+                }
             }
+            // default template not found, return `None`
+            Ok(None)
         }
     }
 }
 
+/// Directories to look for the default template in: the project root, then each parent up to
+/// the workspace root when the project is a workspace member
+fn default_template_dirs(project_root: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![project_root.to_path_buf()];
+    if let Some(workspace_root) = project::find_workspace_root(project_root, None) {
+        if workspace_root != project_root {
+            for dir in project_root.ancestors().skip(1) {
+                dirs.push(dir.to_path_buf());
+                if dir == workspace_root {
+                    break;
+                }
+            }
+        }
+    }
+    dirs
+}
+
+/// Resolve the `{{extends}}` chain of a template read from `path`
+fn resolve_template_file(path: &Path, content: &str) -> Result<String, String> {
+    let dir = path.parent().unwrap_or(Path::new(""));
+    cargo_readme::resolve_template(content, dir)
+}
+
+/// Get the content of a template given as `builtin:NAME`
+pub fn get_builtin_template(template: &str) -> Result<&'static str, String> {
+    let name = template.strip_prefix(BUILTIN_PREFIX).ok_or_else(|| {
+        format!(
+            "Expected a builtin template in the form `{}NAME`, got `{}`",
+            BUILTIN_PREFIX, template
+        )
+    })?;
+
+    cargo_readme::builtin_template(name).map(|t| t.content)
+}
+
+/// Parse a `--set key=value` argument
+pub fn parse_var(arg: &str) -> Result<(String, String), String> {
+    arg.split_once('=')
+        .map(|(key, value)| (key.trim().to_owned(), value.to_owned()))
+        .ok_or_else(|| format!("expected `key=value`, got `{}`", arg))
+}
+
+/// Parse a `--locale` argument, which becomes part of file names
+pub fn parse_locale(arg: &str) -> Result<String, String> {
+    cargo_readme::check_locale(arg).map(|()| arg.to_owned())
+}
+
+/// Insert the locale before the extension of the output file: `README.md` becomes `README.zh.md`
+pub fn localized_output(output: &str, locale: &str) -> String {
+    let path = Path::new(output);
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy())
+        .unwrap_or_default();
+    let name = match path.extension() {
+        Some(extension) => format!("{}.{}.{}", stem, locale, extension.to_string_lossy()),
+        None => format!("{}.{}", stem, locale),
+    };
+    path.with_file_name(name).to_string_lossy().into_owned()
+}
+
+/// Read template variables from a TOML file of `key = "value"` pairs
+pub fn get_var_file(
+    project_root: &Path,
+    var_file: &str,
+) -> Result<BTreeMap<String, String>, String> {
+    let var_file = project_root.join(var_file);
+    let text = std::fs::read_to_string(&var_file).map_err(|e| {
+        format!(
+            "Could not read variables file '{}': {}",
+            var_file.to_string_lossy(),
+            e
+        )
+    })?;
+
+    toml::from_str(&text).map_err(|e| {
+        format!(
+            "Could not read variables file '{}': {}",
+            var_file.to_string_lossy(),
+            e
+        )
+    })
+}
+
 /// Write result to output, either stdout or destination file
 pub fn write_output(dest: &mut Option<File>, readme: String) -> Result<(), String> {
     match dest.as_mut() {
@@ -98,9 +201,8 @@ pub fn write_output(dest: &mut Option<File>, readme: String) -> Result<(), Strin
 /// - file defined in the `[lib]` section of Cargo.toml
 /// - file defined in the `[[bin]]` section of Cargo.toml, if there is only one
 ///   - if there is more than one `[[bin]]`, an error is returned
-pub fn find_entrypoint(current_dir: &Path) -> Result<File, String> {
-    let manifest = get_manifest(current_dir)?;
-    let entrypoint = project::find_entrypoint(current_dir, &manifest)?;
+pub fn find_entrypoint(current_dir: &Path, manifest: &Manifest) -> Result<File, String> {
+    let entrypoint = project::find_entrypoint(current_dir, manifest)?;
 
     File::open(current_dir.join(entrypoint)).map_err(|e| format!("{}", e))
 }
@@ -78,6 +78,13 @@
 //! - code block became "```rust"
 //! - hidden line `# assert_eq!(4, sum2(2, 2));` was removed
 //!
+//! More can be added around the docs without writing a template: `--title TEXT` replaces the
+//! crate name in the title, `--subtitle` adds the `description` from `Cargo.toml` below it,
+//! `--docs-link` adds a link to the documentation on docs.rs, and `--install-section` appends an
+//! "Installation" section with `cargo install` for a binary crate or the `[dependencies]` line
+//! for a library. `--centered-header` puts the title, description, badges and docs link in a
+//! centered HTML block.
+//!
 //! `cargo-readme` also supports multiline doc comments `/*! */` (but you cannot mix styles):
 //!
 //! ~~~ignore
@@ -142,6 +149,176 @@
 //! By default, `README.tpl` will be used as the template, but you can override it using the
 //! `--template` to choose a different template or `--no-template` to disable it.
 //!
+//! Instead of the bare `{{license}}` expression, a template can use `{{license_section}}`, which
+//! spells the expression out: `MIT OR Apache-2.0` becomes a list of both licenses followed by "at
+//! your option", and each license links to its `LICENSE-MIT`/`LICENSE-APACHE` (or `LICENSE`)
+//! file in the project root when there is one, or to its SPDX page otherwise. Without a template,
+//! `--license-section` appends the same text under a `## License` heading in place of the
+//! license line.
+//!
+//! A template can also show what changed in the documented version with `{{changelog}}`. It is
+//! replaced by the entry of a [Keep a Changelog](https://keepachangelog.com) style `CHANGELOG.md`
+//! whose heading matches the version in `Cargo.toml`, such as `## [1.2.0] - 2024-05-01`, with
+//! its subsections moved below the heading that precedes `{{changelog}}` in the template:
+//!
+//! ```tpl
+//! ## What's new in {{version}}
+//!
+//! {{changelog}}
+//! ```
+//!
+//! With `--changelog-entries N`, it lists the latest N releases instead, headings included. When
+//! there is no `CHANGELOG.md` or no entry matches, `{{changelog}}` is left empty and a warning is
+//! printed (an error with `--strict`).
+//!
+//! The state of the local git repository is available as `{{git.commit}}`,
+//! `{{git.short_commit}}`, `{{git.tag}}` (a tag pointing at the current commit), `{{git.branch}}`,
+//! `{{git.default_branch}}` and `{{git.remote_url}}`. They are read from the `.git` directory of
+//! the project or any of its parents, such as the workspace root, without running git or
+//! accessing the network. Outside a repository, or when a value is unknown (e.g. no tag on the
+//! current commit), the variable is left empty with a warning, or an error with `--strict`.
+//!
+//! To show the real `--help` text of a command line tool, a template can embed the output of a
+//! command with `{{exec "cargo run -q -- --help"}}`. The command runs in the project root,
+//! without a shell (group words with single quotes), and its output is inserted in a code block;
+//! a command that fails makes the generation fail. Since templates should not run arbitrary
+//! commands, this only works when `--allow-exec` is passed.
+//!
+//! `{{summary}}` is the first paragraph of the documentation, the same one rustdoc uses as the
+//! crate's summary, which makes it handy for a header line. To catch `package.description`
+//! drifting away from it, run `cargo readme --check-description`: it fails when the two differ,
+//! ignoring markdown formatting and a final period. `--sync-description` writes the summary to
+//! `description` instead, leaving the rest of `Cargo.toml` untouched. Add `--first-sentence` to
+//! either to use only the first sentence of the summary, for a paragraph that goes on after a
+//! one-sentence description.
+//!
+//! You can add variables of your own, such as `{{msrv_note}}` or `{{support_channel}}`, without
+//! editing the template for each crate. They are read, from lowest to highest precedence, from
+//! the `vars` table of `[workspace.metadata.readme]` and `[package.metadata.readme]` (see
+//! [Configuration](#configuration)), from a TOML file of `key = "value"` pairs given with
+//! `--var-file`, and from repeated `--set key=value` flags. They cannot replace the built-in
+//! variables.
+//!
+//! `cargo-readme` also ships a few templates of its own, so a new crate gets a complete README
+//! without copying a `README.tpl` around. Select one with `--template builtin:NAME`:
+//!
+//! | Name | Adds around `{{readme}}` |
+//! |---|---|
+//! | `default` | badges, title and a license line, like the output without a template |
+//! | `minimal` | the title only |
+//! | `dual-license` | the usual MIT OR Apache-2.0 license and contribution sections |
+//! | `binary` | an installation section using `cargo install` |
+//! | `library` | a usage section with the `[dependencies]` line for the current version |
+//!
+//! `cargo readme --list-templates` lists them, and `cargo readme --print-template builtin:NAME`
+//! prints one of them, as a starting point for your own template.
+//!
+//! In a workspace, members can share a house style. A member without a `README.tpl` uses the
+//! first one found in the directories above it, up to the workspace root. A template can also
+//! start from another one with `{{extends "path"}}`, relative to the template's directory, and
+//! replace the regions the parent marks as blocks:
+//!
+//! ```tpl
+//! {{extends "../README.member.tpl"}}
+//!
+//! {{#block body}}
+//! {{readme}}
+//!
+//! Notes specific to this crate.
+//! {{/block}}
+//! ```
+//!
+//! In the parent, the content between `{{#block body}}` and `{{/block}}` is the default used when
+//! a template extending it does not override the block. Anything outside the blocks of the
+//! extending template is ignored.
+//!
+//! A template can carry the settings it was written for in a front matter block at the very start
+//! of the file: YAML between two `---` lines, or TOML between two `+++` lines, with the same keys
+//! as [`[package.metadata.readme]`](#configuration) except `template`, `no-template`, `badge` and
+//! `auto-badges`:
+//!
+//! ```tpl
+//! ---
+//! no-indent-headings: true
+//! strict: true
+//! output: README.md
+//! badge-style:
+//!   style: flat-square
+//! ---
+//!
+//! # {{crate}}
+//!
+//! {{readme}}
+//! ```
+//!
+//! The settings apply only when the template is used, taking precedence over `Cargo.toml` but not
+//! over the command line. The block itself is left out of the output. The front matter of a
+//! template that extends another one is merged over its parent's.
+//!
+//! The README of a workspace root can list the crates of the workspace without a hand-maintained
+//! list: a template repeats the content between `{{#each members}}` and `{{/each}}` for every
+//! member, with `{{name}}`, `{{version}}`, `{{description}}`, `{{summary}}`, `{{badges}}` (on a
+//! single line) and `{{path}}` (relative to the workspace root) set to the member's:
+//!
+//! ```tpl
+//! | Crate | Version | Description |
+//! |---|---|---|
+//! {{#each members}}
+//! | [{{name}}]({{path}}) | {{version}} | {{summary}} |
+//! {{/each}}
+//! ```
+//!
+//! The members are the root package, if any, and the crates matched by `workspace.members`. Such
+//! a template does not need `{{readme}}`, so it also works in the root of a virtual workspace,
+//! which has no docs of its own; there, `{{version}}` and `{{license}}` come from
+//! `[workspace.package]`.
+//!
+//! Placeholders that are not one of the variables above are left in the output as they are. Pass
+//! `--strict` to reject them instead: a typo such as `{{licence}}` is then reported with its line,
+//! column and the closest known variable, and a template that uses `{{badges}}` or `{{license}}`
+//! without the matching entries in `Cargo.toml` fails rather than printing a warning.
+//!
+//! # Localized READMEs
+//!
+//! To publish the README in other languages, put the translated sections of the docs in
+//! `docs/i18n/<locale>.md` and pass `--locale <locale>`:
+//!
+//! ```markdown
+//! 这是我很棒的 crate
+//!
+//! # 示例 {#examples}
+//!
+//! ...
+//! ```
+//!
+//! Each section replaces the section of the docs with the same heading anchor, the one GitHub
+//! would give it (`# Examples` is `examples`). Give it after the translated heading as
+//! `{#examples}`; without it, the anchor of the translated heading is used. Text before the first
+//! heading translates the introduction, and sections without a translation stay in the source
+//! language. The strings `cargo-readme` adds itself, such as "License:", are translated for `zh`
+//! and `ja`. When writing to a file, the locale is added before its extension: `--locale zh
+//! --output README.md` writes `README.zh.md`.
+//!
+//! # Configuration
+//!
+//! Instead of repeating the same flags in every CI job, set them in `Cargo.toml`. Each key is
+//! named after its command line flag:
+//!
+//! ```toml
+//! [package.metadata.readme]
+//! no-license = true
+//! template = "builtin:dual-license"
+//! output = "README.md"
+//! ```
+//!
+//! The available keys are `no-badges`, `no-indent-headings`, `no-license`, `license-section`,
+//! `changelog-entries`, `no-template`, `no-title`, `no-comment-extraction`, `strict`, `title`,
+//! `subtitle`, `docs-link`, `install-section`, `centered-header`, `auto-badges`, `badge-layout`,
+//! `input`, `output` and `template`, plus a `vars` table of template variables, and `badge` entries and
+//! a `badge-style` table (see [Badges](#badges)). Paths are relative to the project root. A `[workspace.metadata.readme]`
+//! table in the workspace root sets defaults for every member, and `[package.metadata.readme]`
+//! overrides it. Flags given on the command line take precedence over both.
+//!
 //! # Badges
 //!
 //! `crates.io` no longer renders the `[badges]` section of `Cargo.toml` on the crate page, but it
@@ -151,22 +328,185 @@
 //!
 //! | Badge key | Required | Optional |
 //! |---|---|---|
-//! | `crates-io` *(extension)* | | `crate` (defaults to the package name) |
+//! | `crates-io` *(extension)* | | `crate` (defaults to the package name), `style`, `logo`, `label`, `color` |
 //! | `appveyor` | `repository` | `branch`, `service` |
 //! | `circle-ci` | `repository` | `branch`, `service` |
-//! | `gitlab` | `repository` | `branch` |
+//! | `gitlab` | `repository` | `branch`, `host`, `instance` |
 //! | `travis-ci` | `repository` | `branch` |
-//! | `github` *(extension)* | `repository` | `workflow` |
-//! | `codecov` | `repository` | `branch`, `service` |
+//! | `github` *(extension)* | `repository` | `workflow-file`, `branch`, `event`, `workflow`, `host` |
+//! | `azure-devops` | `project`, `pipeline` | `build` |
+//! | `bitbucket-pipelines` | `repository`, `branch` | `style`, `logo`, `label`, `color` |
+//! | `cirrus-ci` | `repository` | `branch` |
+//! | `gitea` *(extension)* | `repository`, `workflow-file`, `host` | `branch`, `event` |
+//! | `forgejo` *(extension)* | `repository`, `workflow-file` | `branch`, `event`, `host` |
+//! | `woodpecker` *(extension)* | `repository`, `host` | `branch` |
+//! | `codecov` | `repository` | `branch`, `service`, `host` |
 //! | `coveralls` | `repository` | `branch`, `service` |
 //! | `is-it-maintained-issue-resolution` | `repository` | |
 //! | `is-it-maintained-open-issues` | `repository` | |
-//! | `maintenance` | `status` | |
+//! | `maintenance` | `status` | `style`, `logo`, `label`, `color` |
 //!
 //! `maintenance` is the only badge the [Cargo manifest reference][manifest] still documents; its
 //! `status` accepts `actively-developed`, `passively-maintained`, `as-is`, `experimental`,
-//! `looking-for-maintainer`, `deprecated`, and `none`. `github` and `crates-io` are `cargo-readme`
-//! extensions. Run `cargo readme --list-badges` to print this list from your terminal.
+//! `looking-for-maintainer`, `deprecated`, and `none`. `github`, `crates-io`, `gitea`, `forgejo`
+//! and `woodpecker` are `cargo-readme` extensions. Run `cargo readme --list-badges` to print this
+//! list from your terminal.
+//!
+//! An unknown badge key or attribute, such as `travis_ci` or `repo`, and a value a badge does not
+//! accept, such as a misspelled `status`, print a warning with the closest known name, or fail
+//! the run with `--strict`. The style attributes described below are only accepted by the badges
+//! served by shields.io.
+//!
+//! The `repository` can be left out when `package.repository` is a GitHub, GitLab, Bitbucket or
+//! Codeberg URL that the badge can point to: its `owner/repo` part is used, and `service` is set
+//! to its host. The `branch`, `master` unless given, defaults to the default branch of the local
+//! git clone when the clone's remote is the badge's repository:
+//!
+//! ```toml
+//! [package]
+//! repository = "https://github.com/owner/repo"
+//!
+//! [badges]
+//! codecov = {}
+//! ```
+//!
+//! For `azure-devops`, `project` is `organization/project`, `pipeline` the name of the pipeline
+//! and `build` the id of its build definition (1 by default).
+//!
+//! Badges of forges that can be self-hosted take the forge from `host`, a host name such as
+//! `host = "gitlab.example.com"` or a base URL: `github` uses github.com unless `host` names a
+//! GitHub Enterprise Server, `gitlab` gitlab.com (`instance` is the older name of its `host`),
+//! and `forgejo` codeberg.org. `gitea` shows the status of a Gitea Actions workflow and
+//! `woodpecker` the status of a Woodpecker CI pipeline; both always need a `host`. The
+//! `repository` of `woodpecker` is the id of the repository on the Woodpecker server. `codecov`
+//! uses codecov.io unless `host` names a self-hosted Codecov, which the `service`s
+//! `github-enterprise`, `bitbucket-server` and `gitlab-enterprise` require.
+//!
+//! The `github` badge shows the status of a GitHub Actions workflow. Give its file name as
+//! `workflow-file = "ci.yml"`, optionally with the `branch` and `event` to report on; the older
+//! `workflow` attribute names the workflow instead, a form GitHub has deprecated. For several
+//! workflows, list their files separated by commas; each gets its own badge, with the same
+//! `branch` and `event`:
+//!
+//! ```toml
+//! [badges]
+//! github = { repository = "owner/repo", workflow-file = "ci.yml, release.yml", branch = "main" }
+//! ```
+//!
+//! Badges for other services, such as deps.rs, a chat server or an internal CI, are defined in
+//! `[[package.metadata.readme.badge]]` entries, with the `alt` text and `image` URL of the badge,
+//! and optionally the `link` it points to and a `title`. The fields can use `{name}`,
+//! `{version}` and `{repository}` for the package's `name`, `version` and `repository`:
+//!
+//! ```toml
+//! [[package.metadata.readme.badge]]
+//! alt = "dependency status"
+//! image = "https://deps.rs/crate/{name}/{version}/status.svg"
+//! link = "https://deps.rs/crate/{name}/{version}"
+//! ```
+//!
+//! They come after the badges of the `[badges]` section, in the order they are listed, the
+//! entries of `[[workspace.metadata.readme.badge]]` first.
+//!
+//! Some badges need nothing but what `Cargo.toml` already says. List them in `auto-badges` to
+//! add them without a `[badges]` entry:
+//!
+//! ```toml
+//! [package.metadata.readme]
+//! auto-badges = ["docs-rs", "license", "msrv"]
+//! ```
+//!
+//! | Name | Shows | Derived from |
+//! |---|---|---|
+//! | `docs-rs` | the documentation on docs.rs | `name` |
+//! | `downloads` | the downloads on crates.io | `name` |
+//! | `license` | the license expression | `license` |
+//! | `msrv` | the minimum supported Rust version | `rust-version` |
+//! | `deps-rs` | the dependency status on deps.rs | `name` and `version` |
+//!
+//! Badges served by shields.io can be styled with a `badge-style` table, whose `style` is one of
+//! `flat`, `flat-square`, `plastic`, `for-the-badge` and `social`, and whose `logo`, `label` and
+//! `color` are passed on to shields.io as they are. A badge overrides them with attributes of
+//! the same names, in its `[badges]` table or its `badge` entry:
+//!
+//! ```toml
+//! [badges]
+//! maintenance = { status = "experimental", style = "for-the-badge" }
+//!
+//! [package.metadata.readme.badge-style]
+//! style = "flat-square"
+//! color = "orange"
+//! ```
+//!
+//! Badges of other services, such as the CI ones, have no such options: the `badge-style` table
+//! leaves them as they are, and style attributes in their `[badges]` tables print a warning.
+//!
+//! In a template, `{{badges}}` places every badge, one per line. To lay them out in rows, place
+//! a single badge with `{{badge.<key>}}`, such as `{{badge.crates-io}}`, or the badges of a
+//! category with `{{badges.<category>}}`:
+//!
+//! | Category | Badges |
+//! |---|---|
+//! | `package` | `crates-io`, `docs-rs`, `downloads`, `license`, `msrv` |
+//! | `ci` | `appveyor`, `circle-ci`, `gitlab`, `travis-ci`, `github`, `azure-devops`, `bitbucket-pipelines`, `cirrus-ci`, `gitea`, `forgejo`, `woodpecker` |
+//! | `coverage` | `codecov`, `coveralls` |
+//! | `maintenance` | `is-it-maintained-issue-resolution`, `is-it-maintained-open-issues`, `maintenance`, `deps-rs` |
+//! | `custom` | the `badge` entries of `[package.metadata.readme]` |
+//!
+//! A placeholder for a badge that is not defined is removed with a warning.
+//!
+//! Badges are placed one per line, which GitHub renders on a single row. `badge-layout` (or
+//! `--badge-layout`) places them otherwise, with `{{badges}}`, `{{badges.<category>}}` and
+//! without a template:
+//!
+//! | Layout | Badges |
+//! |---|---|
+//! | `lines` | one per line, the default |
+//! | `inline` | on a single line, separated by spaces |
+//! | `html` | in a centered `<p align="center">` block of `<img>` tags with explicit heights |
+//! | `reference` | as reference-style links, with the URLs defined at the bottom of the README |
+//!
+//! The labels of the `reference` layout are named after the badge keys, such as
+//! `[badge-crates-io]`, and keep the long URLs out of the top of the README, where they make for
+//! hard to read diffs.
+//!
+//! When `cargo-readme` is used as a library, other `[badges]` keys can be rendered by
+//! implementing `BadgeProvider` and registering it in the `BadgeRegistry` given to
+//! `generate_readme_with`. The built-in badges are providers too, so one can also take the place of
+//! a built-in badge by using its key. `[badges]` entries are checked against the `BadgeInfo`
+//! of their provider, and badges of added providers come after the built-in ones:
+//!
+//! ```rust
+//! use cargo_readme::{BadgeAttrs, BadgeCategory, BadgeContext, BadgeInfo, BadgeProvider};
+//!
+//! struct Matrix;
+//!
+//! const MATRIX: BadgeInfo = BadgeInfo {
+//!     key: "matrix",
+//!     official: false,
+//!     category: BadgeCategory::Custom,
+//!     required: &["room"],
+//!     optional: &[],
+//!     values: &[],
+//! };
+//!
+//! impl BadgeProvider for Matrix {
+//!     fn info(&self) -> &BadgeInfo {
+//!         &MATRIX
+//!     }
+//!
+//!     fn render(&self, attrs: BadgeAttrs, _: &BadgeContext) -> Result<String, String> {
+//!         let room = attrs.get("room").ok_or("badge `matrix` needs a `room`")?;
+//!         Ok(format!(
+//!             "[![Matrix](https://img.shields.io/matrix/{room}.svg)](https://matrix.to/#/#{room})"
+//!         ))
+//!     }
+//! }
+//!
+//! let mut badges = cargo_readme::BadgeRegistry::new();
+//! badges.register(Matrix);
+//! # assert!(badges.get("matrix").is_some());
+//! ```
 //!
 //! [manifest]: https://doc.rust-lang.org/cargo/reference/manifest.html#the-badges-section
 //!
@@ -185,8 +525,15 @@
 
 mod config;
 mod readme;
+mod suggest;
 
-pub use config::get_manifest;
 pub use config::project;
-pub use config::{supported_badges, BadgeInfo};
-pub use readme::generate_readme;
+pub use config::{auto_badges, supported_badges, AutoBadgeInfo, Badge, BadgeCategory, BadgeInfo};
+pub use config::{get_manifest, get_manifest_with_badges, read_manifest, Manifest};
+pub use config::{BadgeAttrs, BadgeContext, BadgeProvider, BadgeRegistry};
+pub use config::{BadgeStyle, CustomBadge, ReadmeConfig};
+pub use readme::{builtin_template, builtin_templates, BuiltinTemplate};
+pub use readme::{check_description, check_locale, generate_readme, generate_readme_with};
+pub use readme::{resolve_template, split_front_matter};
+pub use readme::{BadgeLayout, BADGE_LAYOUTS};
+pub use readme::{GenerateOptions, Layout};
@@ -2,7 +2,10 @@
 
 use clap::Parser;
 use std::io;
-use std::io::Write;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use cargo_readme::{BadgeLayout, BadgeRegistry, GenerateOptions, Layout, ReadmeConfig};
 
 mod helper;
 
@@ -33,7 +36,10 @@ enum Command {
 }
 
 /// Generate README.md from doc comments
-#[derive(Debug, Parser)]
+///
+/// Options not given here are read from `[package.metadata.readme]`, then
+/// `[workspace.metadata.readme]`, in `Cargo.toml`.
+#[derive(Debug, Clone, Parser)]
 #[clap(author, version, about)]
 struct ReadmeArgs {
     /// Do not prepend badges line.
@@ -54,6 +60,18 @@ struct ReadmeArgs {
     #[clap(long)]
     no_license: bool,
 
+    /// Append a license section generated from the license expression instead of the license
+    /// line: dual licenses get the "at your option" wording, and each license links to its
+    /// `LICENSE-*` file when there is one, or to its SPDX page.
+    /// Ignored when using a template, use `{{license_section}}` there.
+    #[clap(long)]
+    license_section: bool,
+
+    /// Make `{{changelog}}` list the latest N released entries of `CHANGELOG.md`, with their
+    /// headings, instead of the notes of the version being documented.
+    #[clap(long, value_name = "N")]
+    changelog_entries: Option<usize>,
+
     /// Ignore template file when generating README.
     /// Only useful to ignore default template `README.tpl`.
     #[clap(long)]
@@ -64,17 +82,92 @@ struct ReadmeArgs {
     #[clap(long)]
     no_title: bool,
 
+    /// Title text to prepend instead of the crate name.
+    /// Ignored when using a template.
+    #[clap(long, value_name = "TEXT")]
+    title: Option<String>,
+
+    /// Add the description from `Cargo.toml` below the title.
+    /// Ignored when using a template.
+    #[clap(long)]
+    subtitle: bool,
+
+    /// Add a link to the crate's documentation on docs.rs below the title.
+    /// Ignored when using a template.
+    #[clap(long)]
+    docs_link: bool,
+
+    /// Append an installation section: `cargo install` for binaries, the `[dependencies]` line
+    /// for libraries.
+    /// Ignored when using a template.
+    #[clap(long)]
+    install_section: bool,
+
+    /// Put the title, description, badges and docs link in a centered HTML block.
+    /// Ignored when using a template.
+    #[clap(long)]
+    centered_header: bool,
+
+    /// How to place the badges: `lines` puts one per line, `inline` separates them with spaces,
+    /// `html` puts them in a centered HTML block, and `reference` uses reference-style links
+    /// whose URLs are defined at the bottom of the README. Defaults to `lines`.
+    #[clap(long, value_name = "LAYOUT", value_parser = str::parse::<BadgeLayout>)]
+    badge_layout: Option<BadgeLayout>,
+
+    /// Generate the README for LOCALE, such as `zh`: the sections translated in
+    /// `docs/i18n/LOCALE.md` replace the ones of the docs, the fixed strings such as "License:"
+    /// are translated, and the output file gets the locale before its extension (`README.zh.md`).
+    #[clap(long, value_parser = helper::parse_locale)]
+    locale: Option<String>,
+
     /// Do not extract docs from comment. Instead, just process the input file
     /// as it is.
     /// By default, this flag is `false`.
     #[clap(long)]
     no_comment_extraction: bool,
 
+    /// Fail on template placeholders that are not known variables, on a missing badge or
+    /// license the template refers to, and on unknown badges, badge attributes and values in
+    /// `[badges]`.
+    /// By default, unknown placeholders are left as they are and missing values only warn.
+    #[clap(long)]
+    strict: bool,
+
+    /// Allow `{{exec "COMMAND"}}` directives in the template to run COMMAND in the project root
+    /// and embed its output. Templates cannot run commands without this flag.
+    #[clap(long)]
+    allow_exec: bool,
+
+    /// Compare `package.description` with the first paragraph of the documentation, then exit.
+    /// Fails when they differ.
+    #[clap(long)]
+    check_description: bool,
+
+    /// Set `package.description` to the first paragraph of the documentation when they differ,
+    /// keeping the rest of `Cargo.toml` as it is, then exit.
+    #[clap(long)]
+    sync_description: bool,
+
+    /// Use only the first sentence of the first paragraph with `--check-description` and
+    /// `--sync-description`, for descriptions that are a single sentence.
+    #[clap(long)]
+    first_sentence: bool,
+
     /// List the badges that can be rendered from the `[badges]` section of `Cargo.toml`,
-    /// along with the attributes each one reads, then exit.
+    /// along with the attributes each one reads, and the ones `auto-badges` can derive from the
+    /// package, then exit.
     #[clap(long)]
     list_badges: bool,
 
+    /// List the builtin templates, with what each one adds around the docs, then exit.
+    #[clap(long)]
+    list_templates: bool,
+
+    /// Print a builtin template, given as `builtin:NAME`, then exit.
+    /// Useful as a starting point for a custom `README.tpl`.
+    #[clap(long, value_name = "TEMPLATE")]
+    print_template: Option<String>,
+
     /// File to read from.
     /// If not provided, will try to use `src/lib.rs`, then `src/main.rs`. If neither file
     /// could be found, will look into `Cargo.toml` for a `[lib]`, then for a single `[[bin]]`.
@@ -91,8 +184,19 @@ struct ReadmeArgs {
     #[clap(long = "project-root", short = 'r')]
     root: Option<String>,
 
+    /// Set a template variable, as `key=value`. Can be repeated.
+    /// Takes precedence over `--var-file` and the `vars` table in `Cargo.toml`.
+    #[clap(long = "set", value_name = "KEY=VALUE", value_parser = helper::parse_var)]
+    vars: Vec<(String, String)>,
+
+    /// TOML file of `key = "value"` template variables, relative to the project root.
+    /// Takes precedence over the `vars` table in `Cargo.toml`.
+    #[clap(long)]
+    var_file: Option<String>,
+
     /// Template used to render the output.
-    /// Default behavior is to use `README.tpl` if it exists.
+    /// Default behavior is to use `README.tpl` if it exists. Use `builtin:NAME` for one of the
+    /// templates shipped with cargo-readme: default, minimal, dual-license, binary, library.
     #[clap(long, short = 't')]
     template: Option<String>,
 }
@@ -103,60 +207,213 @@ fn execute(args: &ReadmeArgs) -> Result<(), String> {
         return list_badges();
     }
 
+    if args.list_templates {
+        return list_templates();
+    }
+
+    if let Some(template) = args.print_template.as_deref() {
+        print!("{}", helper::get_builtin_template(template)?);
+        return Ok(());
+    }
+
     // get project root
     let project_root = helper::get_project_root(args.root.as_deref())?;
 
-    // get source file
-    let mut source = helper::get_source(&project_root, args.input.as_deref())?;
+    // read `Cargo.toml` once, its badges are rendered along with the readme
+    let cargo = cargo_readme::read_manifest(&project_root)?;
+
+    // get template, the settings it carries take precedence over the ones from `Cargo.toml`
+    let template = select_template(args, &cargo.config);
+    let template = match template {
+        Some(template) => helper::get_template(&project_root, template.as_deref())?,
+        None => None,
+    };
+    let (front_matter, template) = match template.as_deref() {
+        Some(template) => {
+            let (front_matter, body) = cargo_readme::split_front_matter(template)?;
+            (front_matter, Some(body))
+        }
+        None => (ReadmeConfig::default(), None),
+    };
+
+    // complete the arguments with the settings from the template and `Cargo.toml`; the badge
+    // style of `Cargo.toml` is applied when its badges are rendered
+    let badge_style = front_matter.badge_style.clone();
+    let args = &apply_config(args, &project_root, front_matter.or(cargo.config.clone()))?;
 
-    // get destination file
-    let mut dest = helper::get_dest(args.output.as_deref())?;
+    // get source file, the root of a virtual workspace has no docs of its own
+    let mut source: Box<dyn Read> = match args.input.as_deref() {
+        None if cargo.virtual_workspace => {
+            if template.is_none() {
+                return Err(format!(
+                    "`{}` is a virtual workspace without docs, use a template listing its \
+                     members with `{{{{#each members}}}}` or give an `--input`",
+                    project_root.display()
+                ));
+            }
+            Box::new(io::empty())
+        }
+        input => Box::new(helper::get_source(&project_root, input, &cargo)?),
+    };
+
+    if args.check_description || args.sync_description {
+        let message = cargo_readme::check_description(
+            &project_root,
+            &cargo,
+            &mut source,
+            !args.no_comment_extraction,
+            args.first_sentence,
+            args.sync_description,
+        )?;
+        println!("{}", message);
+        return Ok(());
+    }
 
-    // get template file
-    let mut template_file = if args.no_template {
-        None
-    } else {
-        helper::get_template_file(&project_root, args.template.as_deref())?
+    // get destination file, one per locale
+    let output = match (args.output.as_deref(), args.locale.as_deref()) {
+        (Some(output), Some(locale)) => Some(helper::localized_output(output, locale)),
+        (output, _) => output.map(str::to_owned),
     };
+    let mut dest = helper::get_dest(output.as_deref())?;
 
-    let add_title = !args.no_title;
-    let add_badges = !args.no_badges;
-    let add_license = !args.no_license;
-    let indent_headings = !args.no_indent_headings;
-    let extract_from_comment = !args.no_comment_extraction;
+    let mut template_file = template.map(str::as_bytes);
+
+    let layout = Layout {
+        add_title: !args.no_title,
+        add_badges: !args.no_badges,
+        add_license: !args.no_license,
+        license_section: args.license_section,
+        title: args.title.clone(),
+        subtitle: args.subtitle,
+        docs_link: args.docs_link,
+        install_section: args.install_section,
+        centered_header: args.centered_header,
+        badge_layout: args.badge_layout.unwrap_or_default(),
+        badge_style,
+    };
+    let options = GenerateOptions {
+        layout,
+        // later definitions of a variable override earlier ones
+        vars: args.vars.iter().cloned().collect(),
+        locale: args.locale.clone(),
+        changelog_entries: args.changelog_entries,
+        indent_headings: !args.no_indent_headings,
+        extract_from_comment: !args.no_comment_extraction,
+        allow_exec: args.allow_exec,
+        strict: args.strict,
+    };
 
     // generate output
-    let readme = cargo_readme::generate_readme(
+    let readme = cargo_readme::generate_readme_with(
         &project_root,
+        cargo,
         &mut source,
         template_file.as_mut(),
-        add_title,
-        add_badges,
-        add_license,
-        indent_headings,
-        extract_from_comment,
+        &BadgeRegistry::new(),
+        &options,
     )?;
 
     helper::write_output(&mut dest, readme)
 }
 
-// Print the supported badges and the attributes each one reads. Format mirrors the
-// `[badges]` table so the output can be copied into a Cargo.toml.
+// Pick the template to render with: `None` when templates are disabled, `Some(None)` for the
+// default `README.tpl`. An explicit `--template` overrides `no-template` from the manifest.
+fn select_template(args: &ReadmeArgs, config: &ReadmeConfig) -> Option<Option<String>> {
+    let no_template =
+        args.no_template || (args.template.is_none() && config.no_template.unwrap_or(false));
+    (!no_template).then(|| args.template.clone().or(config.template.clone()))
+}
+
+// Fill in the options not given on the command line from the front matter of the template and the
+// `[package.metadata.readme]` and `[workspace.metadata.readme]` tables, already merged in
+// `config`. Flags can only be switched on from the command line, so a flag already set there wins.
+fn apply_config(
+    args: &ReadmeArgs,
+    project_root: &Path,
+    config: ReadmeConfig,
+) -> Result<ReadmeArgs, String> {
+    let mut args = args.clone();
+
+    args.no_badges |= config.no_badges.unwrap_or(false);
+    args.no_indent_headings |= config.no_indent_headings.unwrap_or(false);
+    args.no_license |= config.no_license.unwrap_or(false);
+    args.license_section |= config.license_section.unwrap_or(false);
+    args.no_title |= config.no_title.unwrap_or(false);
+    args.no_comment_extraction |= config.no_comment_extraction.unwrap_or(false);
+    args.strict |= config.strict.unwrap_or(false);
+    args.subtitle |= config.subtitle.unwrap_or(false);
+    args.docs_link |= config.docs_link.unwrap_or(false);
+    args.install_section |= config.install_section.unwrap_or(false);
+    args.centered_header |= config.centered_header.unwrap_or(false);
+
+    args.title = args.title.or(config.title);
+    args.input = args.input.or(config.input);
+    args.changelog_entries = args.changelog_entries.or(config.changelog_entries);
+    if args.badge_layout.is_none() {
+        args.badge_layout = config.badge_layout.as_deref().map(str::parse).transpose()?;
+    }
+
+    // `--output` is relative to the current directory, `output` to the project root
+    args.output = args.output.or_else(|| {
+        config
+            .output
+            .map(|output| project_root.join(output).to_string_lossy().into_owned())
+    });
+
+    // template variables, from lowest to highest precedence: manifest, front matter,
+    // `--var-file`, `--set`
+    let mut vars: Vec<(String, String)> = config.vars.into_iter().collect();
+    if let Some(var_file) = args.var_file.as_deref() {
+        vars.extend(helper::get_var_file(project_root, var_file)?);
+    }
+    vars.append(&mut args.vars);
+    args.vars = vars;
+
+    Ok(args)
+}
+
+// Print the supported badges and the attributes each one reads, then the badges derived from the
+// package. Format mirrors the `[badges]` table so the output can be copied into a Cargo.toml.
 fn list_badges() -> Result<(), String> {
     let mut out = String::from("Supported badges (add under [badges] in Cargo.toml):\n\n");
-    for badge in cargo_readme::supported_badges() {
+    for badge in BadgeRegistry::new().providers().map(|p| p.info()) {
         out.push_str("  ");
         out.push_str(badge.key);
         if !badge.official {
             out.push_str("  (cargo-readme extension)");
         }
         out.push('\n');
+        out.push_str(&format!("    category: {}\n", badge.category.name()));
         if !badge.required.is_empty() {
             out.push_str(&format!("    required: {}\n", badge.required.join(", ")));
         }
         if !badge.optional.is_empty() {
             out.push_str(&format!("    optional: {}\n", badge.optional.join(", ")));
         }
+        for (attr, values) in badge.values {
+            out.push_str(&format!("    {}: one of {}\n", attr, values.join(", ")));
+        }
+    }
+
+    out.push_str(
+        "\nBadges derived from the package (list in `auto-badges` under \
+         [package.metadata.readme]):\n\n",
+    );
+    for badge in cargo_readme::auto_badges() {
+        out.push_str(&format!("  {}\n", badge.key));
+        out.push_str(&format!("    category: {}\n", badge.category.name()));
+        out.push_str(&format!("    from: {}\n", badge.source));
+    }
+    print!("{}", out);
+    Ok(())
+}
+
+// Print the builtin templates and what each one adds around `{{readme}}`.
+fn list_templates() -> Result<(), String> {
+    let mut out = String::from("Builtin templates (use with --template builtin:NAME):\n\n");
+    for template in cargo_readme::builtin_templates() {
+        out.push_str(&format!("  {}\n", template.name));
+        out.push_str(&format!("    {}\n", template.description));
     }
     print!("{}", out);
     Ok(())
@@ -0,0 +1,317 @@
+//! Badge layout
+//!
+//! How badges are placed, by `{{badges}}`, `{{badges.<category>}}` and when they are prepended to
+//! the output: one per line, separated by spaces, in a centered HTML block, or as reference-style
+//! links whose URLs are defined at the bottom of the README.
+
+use regex::Regex;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use crate::config::Badge;
+use crate::suggest;
+
+// A badge as cargo-readme renders it, `![alt](image "title")` optionally wrapped in a link, which
+// can have a title of its own.
+static RE_BADGE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"^(?:\[!\[(?P<lalt>[^\]]*)\]\((?P<limage>[^ )]+)(?: "(?P<ltitle>[^"]*)")?\)\]\((?P<link>[^ )]+)(?: "(?P<linktitle>[^"]*)")?\)|!\[(?P<alt>[^\]]*)\]\((?P<image>[^ )]+)(?: "(?P<title>[^"]*)")?\))$"#,
+    )
+    .unwrap()
+});
+
+/// Names of the layouts, as given to `--badge-layout`
+pub const BADGE_LAYOUTS: &[&str] = &["lines", "inline", "html", "reference"];
+
+/// How the badges are placed
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeLayout {
+    /// One badge per line, which GitHub renders on a single row.
+    #[default]
+    Lines,
+    /// Badges on a single line, separated by spaces.
+    Inline,
+    /// A centered `<p>` block of `<img>` tags with explicit heights.
+    Html,
+    /// Reference-style links, with the URLs defined at the bottom of the README.
+    Reference,
+}
+
+impl FromStr for BadgeLayout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lines" => Ok(BadgeLayout::Lines),
+            "inline" => Ok(BadgeLayout::Inline),
+            "html" => Ok(BadgeLayout::Html),
+            "reference" => Ok(BadgeLayout::Reference),
+            _ => {
+                let mut msg = format!(
+                    "Unknown badge layout `{s}`, expected one of: {}",
+                    BADGE_LAYOUTS.join(", ")
+                );
+                if let Some(known) = suggest::did_you_mean(s, BADGE_LAYOUTS.iter().copied()) {
+                    msg.push_str(&format!(" (did you mean `{known}`?)"));
+                }
+                Err(msg)
+            }
+        }
+    }
+}
+
+/// The badges of a project, rendered for a layout
+pub struct LaidOutBadges {
+    /// Each badge, in the order of the manifest.
+    pub badges: Vec<String>,
+    /// Link reference definitions of the `reference` layout, as `(label, definition)`.
+    pub definitions: Vec<(String, String)>,
+}
+
+/// Render each badge for `layout`
+///
+/// In the `reference` layout, the labels are `badge-<key>`, numbered from the second badge of a
+/// key on, such as `badge-github-2`.
+pub fn lay_out(badges: &[Badge], layout: BadgeLayout) -> LaidOutBadges {
+    let mut laid_out = LaidOutBadges {
+        badges: Vec::with_capacity(badges.len()),
+        definitions: Vec::new(),
+    };
+
+    for (i, badge) in badges.iter().enumerate() {
+        let parts = match (layout, parse(&badge.markdown)) {
+            (BadgeLayout::Html | BadgeLayout::Reference, Some(parts)) => parts,
+            // the badges laid out as markdown are left as they are
+            _ => {
+                laid_out.badges.push(badge.markdown.clone());
+                continue;
+            }
+        };
+
+        if layout == BadgeLayout::Html {
+            laid_out.badges.push(html(&parts));
+            continue;
+        }
+
+        let count = badges[..i].iter().filter(|b| b.key == badge.key).count();
+        let label = match count {
+            0 => format!("badge-{}", badge.key),
+            n => format!("badge-{}-{}", badge.key, n + 1),
+        };
+        let image = with_title(parts.image, parts.title);
+        laid_out
+            .definitions
+            .push((label.clone(), format!("[{label}]: {image}")));
+        match parts.link {
+            Some(link) => {
+                let link = with_title(link, parts.link_title);
+                laid_out
+                    .definitions
+                    .push((format!("{label}-link"), format!("[{label}-link]: {link}")));
+                laid_out
+                    .badges
+                    .push(format!("[![{}][{label}]][{label}-link]", parts.alt));
+            }
+            None => laid_out.badges.push(format!("![{}][{label}]", parts.alt)),
+        }
+    }
+
+    laid_out
+}
+
+/// The destination of a link reference definition, with its title if any
+fn with_title(url: &str, title: Option<&str>) -> String {
+    match title {
+        Some(title) => format!("{url} \"{title}\""),
+        None => url.to_owned(),
+    }
+}
+
+/// Join badges into the block `layout` places them in
+pub fn join(badges: &[&str], layout: BadgeLayout) -> String {
+    if badges.is_empty() {
+        return String::new();
+    }
+
+    match layout {
+        BadgeLayout::Lines | BadgeLayout::Reference => badges.join("\n"),
+        BadgeLayout::Inline => badges.join(" "),
+        BadgeLayout::Html => format!("<p align=\"center\">\n{}\n</p>", badges.join("\n")),
+    }
+}
+
+/// Append the definitions of the labels `readme` refers to
+pub fn append_definitions(readme: String, definitions: &[(String, String)]) -> String {
+    let used: Vec<&str> = definitions
+        .iter()
+        .filter(|(label, _)| readme.contains(&format!("[{label}]")))
+        .map(|(_, definition)| definition.as_str())
+        .collect();
+
+    match (used.is_empty(), readme.trim().is_empty()) {
+        (true, _) => readme,
+        (false, true) => used.join("\n"),
+        (false, false) => format!("{}\n\n{}", readme, used.join("\n")),
+    }
+}
+
+struct BadgeParts<'a> {
+    alt: &'a str,
+    image: &'a str,
+    title: Option<&'a str>,
+    link: Option<&'a str>,
+    link_title: Option<&'a str>,
+}
+
+fn parse(markdown: &str) -> Option<BadgeParts<'_>> {
+    let cap = RE_BADGE.captures(markdown)?;
+    let get = |name: &str| cap.name(name).map(|m| m.as_str());
+
+    Some(match get("link") {
+        Some(link) => BadgeParts {
+            alt: get("lalt")?,
+            image: get("limage")?,
+            title: get("ltitle"),
+            link: Some(link),
+            link_title: get("linktitle"),
+        },
+        None => BadgeParts {
+            alt: get("alt")?,
+            image: get("image")?,
+            title: get("title"),
+            link: None,
+            link_title: None,
+        },
+    })
+}
+
+/// `<img>` tag of a badge, linked when it has a link
+///
+/// shields.io badges are 20 pixels high, 28 in the `for-the-badge` style.
+fn html(parts: &BadgeParts) -> String {
+    let height = if parts.image.contains("style=for-the-badge") {
+        28
+    } else {
+        20
+    };
+    let title = parts
+        .title
+        .map(|title| format!(" title=\"{}\"", escape(title)))
+        .unwrap_or_default();
+    let img = format!(
+        "<img src=\"{}\" alt=\"{}\"{title} height=\"{height}\">",
+        escape(parts.image),
+        escape(parts.alt)
+    );
+
+    let link_title = parts
+        .link_title
+        .map(|title| format!(" title=\"{}\"", escape(title)))
+        .unwrap_or_default();
+    match parts.link {
+        Some(link) => format!("<a href=\"{}\"{link_title}>{img}</a>", escape(link)),
+        None => img,
+    }
+}
+
+/// Escape text for an HTML attribute value
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BadgeCategory;
+
+    fn badges() -> Vec<Badge> {
+        [
+            ("crates-io", "[![Crates.io](https://img.shields.io/crates/v/a.svg)](https://crates.io/crates/a)"),
+            ("github", "[![CI](https://github.com/o/r/actions/workflows/ci.yml/badge.svg)](https://github.com/o/r/actions/workflows/ci.yml)"),
+            ("github", "[![Release](https://github.com/o/r/actions/workflows/release.yml/badge.svg)](https://github.com/o/r/actions/workflows/release.yml)"),
+            ("maintenance", "![Maintenance](https://img.shields.io/badge/maintenance-experimental-blue.svg?style=for-the-badge&color=orange)"),
+        ]
+        .iter()
+        .map(|&(key, markdown)| Badge {
+            key,
+            category: BadgeCategory::Package,
+            markdown: markdown.to_string(),
+        })
+        .collect()
+    }
+
+    #[test]
+    fn reference_labels_numbered_per_key() {
+        let laid_out = lay_out(&badges(), BadgeLayout::Reference);
+        assert_eq!(
+            vec![
+                "[![Crates.io][badge-crates-io]][badge-crates-io-link]",
+                "[![CI][badge-github]][badge-github-link]",
+                "[![Release][badge-github-2]][badge-github-2-link]",
+                "![Maintenance][badge-maintenance]",
+            ],
+            laid_out.badges
+        );
+        assert_eq!(
+            "[badge-maintenance]: https://img.shields.io/badge/maintenance-experimental-blue.svg?style=for-the-badge&color=orange",
+            laid_out.definitions.last().unwrap().1
+        );
+    }
+
+    #[test]
+    fn html_heights_and_escaping() {
+        let laid_out = lay_out(&badges(), BadgeLayout::Html);
+        assert_eq!(
+            "<a href=\"https://crates.io/crates/a\"><img src=\"https://img.shields.io/crates/v/a.svg\" alt=\"Crates.io\" height=\"20\"></a>",
+            laid_out.badges[0]
+        );
+        assert_eq!(
+            "<img src=\"https://img.shields.io/badge/maintenance-experimental-blue.svg?style=for-the-badge&amp;color=orange\" alt=\"Maintenance\" height=\"28\">",
+            laid_out.badges[3]
+        );
+    }
+
+    #[test]
+    fn link_title_kept_apart_from_link() {
+        let badges = [Badge {
+            key: "is-it-maintained-open-issues",
+            category: BadgeCategory::Maintenance,
+            markdown: "[![Percentage of issues still open](https://isitmaintained.com/badge/open/o/r.svg)](https://isitmaintained.com/project/o/r \"Percentage of issues still open\")".to_owned(),
+        }];
+
+        let laid_out = lay_out(&badges, BadgeLayout::Html);
+        assert_eq!(
+            "<a href=\"https://isitmaintained.com/project/o/r\" title=\"Percentage of issues still open\"><img src=\"https://isitmaintained.com/badge/open/o/r.svg\" alt=\"Percentage of issues still open\" height=\"20\"></a>",
+            laid_out.badges[0]
+        );
+
+        let laid_out = lay_out(&badges, BadgeLayout::Reference);
+        assert_eq!(
+            "[badge-is-it-maintained-open-issues-link]: https://isitmaintained.com/project/o/r \"Percentage of issues still open\"",
+            laid_out.definitions[1].1
+        );
+    }
+
+    #[test]
+    fn unused_definitions_left_out() {
+        let definitions = vec![
+            ("badge-a".to_owned(), "[badge-a]: a.svg".to_owned()),
+            ("badge-b".to_owned(), "[badge-b]: b.svg".to_owned()),
+        ];
+        assert_eq!(
+            "![A][badge-a]\n\n[badge-a]: a.svg",
+            append_definitions("![A][badge-a]".to_owned(), &definitions)
+        );
+    }
+
+    #[test]
+    fn unknown_layout_should_fail() {
+        assert_eq!(
+            "Unknown badge layout `inlne`, expected one of: lines, inline, html, reference (did you mean `inline`?)",
+            "inlne".parse::<BadgeLayout>().unwrap_err()
+        );
+    }
+}
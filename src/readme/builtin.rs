@@ -0,0 +1,90 @@
+//! Templates shipped inside the binary
+//!
+//! Selected with `--template builtin:NAME`, or dumped with `--print-template builtin:NAME` to be
+//! used as a starting point for a project's own `README.tpl`. `--list-templates` lists them.
+
+use crate::suggest;
+
+/// A template bundled with cargo-readme.
+pub struct BuiltinTemplate {
+    /// The name used after `builtin:`, e.g. `"dual-license"`.
+    pub name: &'static str,
+    /// One line describing what the template adds around `{{readme}}`.
+    pub description: &'static str,
+    /// The template itself.
+    pub content: &'static str,
+}
+
+/// Every builtin template, in the order they are listed.
+pub const BUILTIN_TEMPLATES: &[BuiltinTemplate] = &[
+    BuiltinTemplate {
+        name: "default",
+        description: "badges, title and a license line, like the output without a template",
+        content: include_str!("templates/default.tpl"),
+    },
+    BuiltinTemplate {
+        name: "minimal",
+        description: "the title only",
+        content: include_str!("templates/minimal.tpl"),
+    },
+    BuiltinTemplate {
+        name: "dual-license",
+        description: "the usual MIT OR Apache-2.0 license and contribution sections",
+        content: include_str!("templates/dual-license.tpl"),
+    },
+    BuiltinTemplate {
+        name: "binary",
+        description: "an installation section using `cargo install`",
+        content: include_str!("templates/binary.tpl"),
+    },
+    BuiltinTemplate {
+        name: "library",
+        description: "a usage section with the `[dependencies]` line for the current version",
+        content: include_str!("templates/library.tpl"),
+    },
+];
+
+/// Look up a builtin template by name
+pub fn builtin_template(name: &str) -> Result<&'static BuiltinTemplate, String> {
+    if let Some(template) = BUILTIN_TEMPLATES.iter().find(|t| t.name == name) {
+        return Ok(template);
+    }
+
+    let names = BUILTIN_TEMPLATES.iter().map(|t| t.name);
+    let mut msg = format!("Unknown builtin template `{}`", name);
+    if let Some(known) = suggest::did_you_mean(name, names.clone()) {
+        msg.push_str(&format!(" (did you mean `builtin:{}`?)", known));
+    }
+    msg.push_str(&format!(
+        ", available templates: {}",
+        names.collect::<Vec<_>>().join(", ")
+    ));
+    Err(msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `process_template` refuses templates without `{{readme}}`, so every builtin must have it.
+    #[test]
+    fn builtin_templates_contain_readme() {
+        for template in BUILTIN_TEMPLATES {
+            assert!(
+                template.content.contains("{{readme}}"),
+                "builtin template `{}` is missing `{{{{readme}}}}`",
+                template.name
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_builtin_template_should_suggest() {
+        let err = builtin_template("dual-licence").err().unwrap();
+        assert_eq!(
+            "Unknown builtin template `dual-licence` (did you mean `builtin:dual-license`?), \
+             available templates: default, minimal, dual-license, binary, library",
+            err
+        );
+    }
+}
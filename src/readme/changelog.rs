@@ -0,0 +1,282 @@
+//! Extract entries from a Keep-a-Changelog style `CHANGELOG.md`
+//!
+//! Each release is a heading such as `## [1.2.0] - 2024-05-01` or `## v1.2.0`, followed by its
+//! `### Added`/`### Fixed`/... subsections. The entries are inserted with their headings shifted
+//! so they nest under the heading that precedes `{{changelog}}` in the template.
+
+use std::path::Path;
+
+const CHANGELOG_FILE: &str = "CHANGELOG.md";
+
+/// One release section of the changelog
+struct Entry<'a> {
+    heading: &'a str,
+    body: Vec<&'a str>,
+}
+
+impl Entry<'_> {
+    /// The version of the release, or `None` for `[Unreleased]`
+    fn version(&self) -> Option<&str> {
+        heading_version(self.heading)
+    }
+}
+
+/// The version named by a release heading such as `## [1.2.0] - 2024-05-01`
+fn heading_version(heading: &str) -> Option<&str> {
+    let text = heading_text(heading);
+    let end = text.find([']', ' ', '(']).unwrap_or(text.len());
+    let version = &text[..end];
+    let version = version.strip_prefix('v').unwrap_or(version);
+    version
+        .starts_with(|c: char| c.is_ascii_digit())
+        .then_some(version)
+}
+
+fn heading_text(heading: &str) -> &str {
+    let text = heading.trim_start_matches('#').trim();
+    text.strip_prefix('[').unwrap_or(text)
+}
+
+/// Get the changelog excerpt for the template
+///
+/// With `entries`, the latest released entries are included with their headings, otherwise the
+/// body of the entry for `version`. Headings are shifted to start below `level`. The error tells
+/// why there is no excerpt, when the changelog is missing or no entry matches.
+pub fn changelog(
+    project_root: &Path,
+    version: &str,
+    entries: Option<usize>,
+    level: usize,
+) -> Result<String, String> {
+    let text = match std::fs::read_to_string(project_root.join(CHANGELOG_FILE)) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(format!("there is no `{CHANGELOG_FILE}`"))
+        }
+        Err(e) => return Err(format!("`{CHANGELOG_FILE}` could not be read: {e}")),
+    };
+
+    excerpt(&text, version, entries, level).ok_or_else(|| match entries {
+        Some(_) => format!("`{CHANGELOG_FILE}` has no released entries"),
+        None => format!("`{CHANGELOG_FILE}` has no entry for the documented version"),
+    })
+}
+
+fn excerpt(text: &str, version: &str, entries: Option<usize>, level: usize) -> Option<String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let (entry_level, parsed) = parse_entries(&lines)?;
+
+    let (excerpt, from_level) = match entries {
+        Some(n) => {
+            let released: Vec<&Entry> = parsed.iter().filter(|e| e.version().is_some()).collect();
+            if released.is_empty() {
+                return None;
+            }
+            let lines: Vec<&str> = released
+                .into_iter()
+                .take(n)
+                .flat_map(|e| std::iter::once(e.heading).chain(e.body.iter().copied()))
+                .collect();
+            (lines, entry_level)
+        }
+        None => {
+            let entry = parsed.iter().find(|e| e.version() == Some(version))?;
+            (entry.body.clone(), entry_level + 1)
+        }
+    };
+
+    let mut excerpt = shift_headings(&excerpt, from_level, level + 1);
+    while excerpt.last().is_some_and(|line| line.trim().is_empty()) {
+        excerpt.pop();
+    }
+
+    let links = link_definitions(&lines, &excerpt);
+    if !links.is_empty() {
+        excerpt.push(String::new());
+        excerpt.extend(links);
+    }
+
+    Some(excerpt.join("\n").trim().to_owned())
+}
+
+/// Split the changelog into release entries, returning their heading level
+fn parse_entries<'a>(lines: &[&'a str]) -> Option<(usize, Vec<Entry<'a>>)> {
+    // the first heading that names a version (or `Unreleased`) sets the level of every entry
+    let entry_level = lines.iter().find_map(|line| {
+        let level = heading_level(line)?;
+        let unreleased = heading_text(line).to_lowercase().starts_with("unreleased");
+        (heading_version(line).is_some() || unreleased).then_some(level)
+    })?;
+
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut in_code = false;
+    for line in lines {
+        if is_fence(line) {
+            in_code = !in_code;
+        }
+        let level = if in_code { None } else { heading_level(line) };
+        match level {
+            Some(level) if level == entry_level => entries.push(Entry {
+                heading: line,
+                body: Vec::new(),
+            }),
+            // a shallower heading ends the list of releases
+            Some(level) if level < entry_level && !entries.is_empty() => break,
+            _ if is_link_definition(line) => {}
+            _ => {
+                if let Some(entry) = entries.last_mut() {
+                    entry.body.push(line);
+                }
+            }
+        }
+    }
+
+    Some((entry_level, entries))
+}
+
+/// Move headings so the ones at `from` end up at `to`, leaving code blocks alone
+fn shift_headings(lines: &[&str], from: usize, to: usize) -> Vec<String> {
+    let mut in_code = false;
+    lines
+        .iter()
+        .map(|line| {
+            if is_fence(line) {
+                in_code = !in_code;
+            }
+            match heading_level(line) {
+                Some(level) if !in_code => {
+                    let level = (level + to).saturating_sub(from).clamp(1, 6);
+                    let text = line.trim_start_matches('#');
+                    format!("{}{}", "#".repeat(level), text)
+                }
+                _ => line.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// The link reference definitions (`[1.0.0]: https://...`) used by the excerpt
+fn link_definitions(lines: &[&str], excerpt: &[String]) -> Vec<String> {
+    lines
+        .iter()
+        .filter(|line| is_link_definition(line))
+        .filter(|line| {
+            let label = &line[..=line.find("]:").unwrap()];
+            excerpt.iter().any(|l| l.contains(label))
+        })
+        .map(|line| line.to_string())
+        .collect()
+}
+
+/// Level of the last heading before `offset` in `text`, or 1 when there is none
+pub fn heading_level_before(text: &str, offset: usize) -> usize {
+    let mut in_code = false;
+    let mut current = 1;
+    for line in text[..offset].lines() {
+        if is_fence(line) {
+            in_code = !in_code;
+        }
+        if let Some(level) = heading_level(line).filter(|_| !in_code) {
+            current = level;
+        }
+    }
+    current
+}
+
+/// Level of a markdown ATX heading (`## Title` is 2)
+fn heading_level(line: &str) -> Option<usize> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let rest = &line[level..];
+    ((1..=6).contains(&level) && (rest.is_empty() || rest.starts_with(' '))).then_some(level)
+}
+
+fn is_fence(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("```") || line.starts_with("~~~")
+}
+
+fn is_link_definition(line: &str) -> bool {
+    line.starts_with('[') && line.contains("]:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHANGELOG: &str = "# Changelog
+
+All notable changes to this project will be documented in this file.
+
+## [Unreleased]
+
+### Added
+- Something new
+
+## [1.1.0] - 2024-05-01
+
+### Fixed
+- A bug ([#12])
+
+```text
+# not a heading
+```
+
+## [1.0.0] - 2024-01-01
+
+### Added
+- Everything
+
+[Unreleased]: https://example.com/compare/v1.1.0...HEAD
+[1.1.0]: https://example.com/compare/v1.0.0...v1.1.0
+[1.0.0]: https://example.com/releases/v1.0.0
+[#12]: https://example.com/issues/12
+";
+
+    #[test]
+    fn excerpt_for_version() {
+        assert_eq!(
+            Some(
+                "### Fixed\n- A bug ([#12])\n\n```text\n# not a heading\n```\n\n\
+                 [#12]: https://example.com/issues/12"
+                    .to_owned()
+            ),
+            excerpt(CHANGELOG, "1.1.0", None, 2)
+        );
+    }
+
+    #[test]
+    fn excerpt_for_missing_version() {
+        assert_eq!(None, excerpt(CHANGELOG, "2.0.0", None, 2));
+    }
+
+    #[test]
+    fn excerpt_latest_entries() {
+        assert_eq!(
+            Some(
+                "### [1.1.0] - 2024-05-01\n\n#### Fixed\n- A bug ([#12])\n\n\
+                 ```text\n# not a heading\n```\n\n\
+                 ### [1.0.0] - 2024-01-01\n\n#### Added\n- Everything\n\n\
+                 [1.1.0]: https://example.com/compare/v1.0.0...v1.1.0\n\
+                 [1.0.0]: https://example.com/releases/v1.0.0\n\
+                 [#12]: https://example.com/issues/12"
+                    .to_owned()
+            ),
+            excerpt(CHANGELOG, "1.1.0", Some(2), 2)
+        );
+    }
+
+    #[test]
+    fn release_heading_version() {
+        assert_eq!(Some("1.2.0"), heading_version("## [1.2.0] - 2024-05-01"));
+        assert_eq!(Some("1.2.0"), heading_version("## v1.2.0 (2024-05-01)"));
+        assert_eq!(None, heading_version("## [Unreleased]"));
+    }
+
+    #[test]
+    fn level_of_preceding_heading() {
+        let template = "# {{crate}}\n\n## What's new\n\n{{changelog}}";
+        let offset = template.find("{{changelog}}").unwrap();
+        assert_eq!(2, heading_level_before(template, offset));
+        assert_eq!(1, heading_level_before("{{changelog}}", 0));
+    }
+}
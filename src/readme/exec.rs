@@ -0,0 +1,170 @@
+//! Embed the output of local commands in the template
+//!
+//! `{{exec "cargo run -q -- --help"}}` runs the command in the project root and is replaced by
+//! its stdout in a code block. Commands are run directly, not through a shell; words can be
+//! grouped with single quotes. Templates only run commands when explicitly allowed.
+//!
+//! The output is put in place once the variables are substituted, so the `{{...}}` a command
+//! prints are kept as they are.
+
+use regex::Regex;
+use std::path::Path;
+use std::process::Command;
+use std::sync::LazyLock;
+
+// `{{exec "..."}}`, capturing the command; `\"` escapes a double quote inside it.
+static RE_EXEC: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\{\{\s*exec\s+"(?P<command>(?:[^"\\]|\\.)*)"\s*\}\}"#).unwrap());
+
+/// Replace every `{{exec "..."}}` directive with a marker standing for the output of its command
+///
+/// Returns the template and the outputs, which [`restore_exec`] puts back in place of the markers.
+pub fn expand_exec(
+    template: &str,
+    project_root: &Path,
+    allow_exec: bool,
+) -> Result<(String, Vec<String>), String> {
+    let mut result = String::with_capacity(template.len());
+    let mut outputs = Vec::new();
+    let mut last = 0;
+
+    for cap in RE_EXEC.captures_iter(template) {
+        let directive = cap.get(0).unwrap();
+        let command = cap["command"].replace("\\\"", "\"");
+
+        if !allow_exec {
+            return Err(format!(
+                "The template runs `{command}`; pass `--allow-exec` to allow templates to run \
+                 commands"
+            ));
+        }
+
+        let output = run(&command, project_root)?;
+        result.push_str(&template[last..directive.start()]);
+        result.push_str(&marker(outputs.len()));
+        outputs.push(format!("```\n{}\n```", output.trim_end()));
+        last = directive.end();
+    }
+
+    result.push_str(&template[last..]);
+    Ok((result, outputs))
+}
+
+/// Put the outputs of the commands back in place of their markers
+pub fn restore_exec(readme: String, outputs: &[String]) -> String {
+    outputs
+        .iter()
+        .enumerate()
+        .fold(readme, |readme, (index, output)| {
+            readme.replace(&marker(index), output)
+        })
+}
+
+// Stands for the output of a command until the variables are substituted. NUL cannot be part of
+// a template or of the docs, so the marker does not clash with them.
+fn marker(index: usize) -> String {
+    format!("\u{0}exec-{index}\u{0}")
+}
+
+/// Run a command in the project root and return its stdout
+fn run(command: &str, project_root: &Path) -> Result<String, String> {
+    let words = split_command(command)?;
+    let (program, args) = words
+        .split_first()
+        .ok_or_else(|| "Empty command in `{{exec}}`".to_owned())?;
+
+    let output = Command::new(program)
+        .args(args)
+        .current_dir(project_root)
+        .output()
+        .map_err(|e| format!("Could not run `{command}`: {e}"))?;
+
+    if !output.status.success() {
+        let mut msg = format!("Command `{command}` failed with {}", output.status);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !stderr.trim().is_empty() {
+            msg.push_str(&format!(":\n{}", stderr.trim_end()));
+        }
+        return Err(msg);
+    }
+
+    String::from_utf8(output.stdout)
+        .map_err(|e| format!("Output of `{command}` is not valid UTF-8: {e}"))
+}
+
+/// Split a command line into words, keeping single-quoted text together
+fn split_command(command: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quoted = false;
+
+    for c in command.chars() {
+        match c {
+            '\'' => {
+                quoted = !quoted;
+                word.get_or_insert_with(String::new);
+            }
+            c if c.is_whitespace() && !quoted => {
+                words.extend(word.take());
+            }
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    if quoted {
+        return Err(format!("Unterminated quote in command `{command}`"));
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_command_words() {
+        assert_eq!(
+            vec!["cargo", "run", "-q", "--", "--help"],
+            split_command("cargo run -q -- --help").unwrap()
+        );
+        assert_eq!(
+            vec!["echo", "two words", ""],
+            split_command("echo  'two words' ''").unwrap()
+        );
+        assert!(split_command("echo 'open").is_err());
+    }
+
+    #[test]
+    fn exec_not_allowed_should_fail() {
+        let result = expand_exec("{{readme}}\n{{exec \"ls\"}}", Path::new("."), false);
+        assert_eq!(
+            "The template runs `ls`; pass `--allow-exec` to allow templates to run commands",
+            result.unwrap_err()
+        );
+    }
+
+    #[test]
+    fn template_without_exec_is_unchanged() {
+        let template = "{{readme}}\n{{crate}}";
+        assert_eq!(
+            (template.to_owned(), Vec::new()),
+            expand_exec(template, Path::new("."), false).unwrap()
+        );
+    }
+
+    #[test]
+    fn output_is_not_substituted() {
+        let (template, outputs) = expand_exec(
+            "{{version}}\n{{exec \"echo {{version}}\"}}",
+            Path::new("."),
+            true,
+        )
+        .unwrap();
+        let readme = template.replace("{{version}}", "0.1.0");
+        assert_eq!(
+            "0.1.0\n```\n{{version}}\n```",
+            restore_exec(readme, &outputs)
+        );
+    }
+}
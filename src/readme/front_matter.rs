@@ -0,0 +1,167 @@
+//! Settings carried by a template in a front matter block
+//!
+//! A template can start with a YAML block between two `---` lines, or a TOML block between two
+//! `+++` lines, using the same keys as `[package.metadata.readme]`. The settings only apply when
+//! the template is used, and the block is not part of the output.
+
+use crate::config::ReadmeConfig;
+
+/// Keys that select the template, which would be meaningless inside it, and the badges, which are
+/// rendered with the rest of `Cargo.toml`
+const FORBIDDEN_KEYS: &[&str] = &["template", "no-template", "badge", "auto-badges"];
+
+/// The language of a front matter block, told by its delimiter lines
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    Yaml,
+    Toml,
+}
+
+impl Format {
+    fn delimiter(self) -> &'static str {
+        match self {
+            Format::Yaml => "---",
+            Format::Toml => "+++",
+        }
+    }
+}
+
+/// Split the front matter from the rest of the template, as a table of settings
+///
+/// Returns `None` when the template does not start with a front matter block. Blank lines
+/// following the block are dropped along with it.
+pub fn split_table(template: &str) -> Result<(Option<toml::Table>, &str), String> {
+    let Some((format, rest)) = strip_opening_line(template) else {
+        return Ok((None, template));
+    };
+
+    let delimiter = format.delimiter();
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == delimiter {
+            let table = parse(format, &rest[..offset])?;
+            let body = rest[offset + line.len()..].trim_start_matches(['\n', '\r']);
+            return Ok((Some(table), body));
+        }
+        offset += line.len();
+    }
+
+    Err(format!(
+        "Unterminated front matter in template: missing the closing `{delimiter}` line"
+    ))
+}
+
+/// Read the front matter of a template, returning its settings and the rest of the template
+///
+/// A template without front matter has default settings.
+pub fn split_front_matter(template: &str) -> Result<(ReadmeConfig, &str), String> {
+    let (table, body) = split_table(template)?;
+    let Some(table) = table else {
+        return Ok((ReadmeConfig::default(), body));
+    };
+
+    if let Some(key) = FORBIDDEN_KEYS.iter().find(|key| table.contains_key(**key)) {
+        return Err(format!(
+            "Invalid front matter in template: `{key}` cannot be set by the template itself"
+        ));
+    }
+
+    let config = toml::Value::Table(table)
+        .try_into()
+        .map_err(|e: toml::de::Error| invalid(&e))?;
+    Ok((config, body))
+}
+
+/// Parse the front matter as a table, whatever its language
+fn parse(format: Format, front_matter: &str) -> Result<toml::Table, String> {
+    match format {
+        Format::Toml => toml::from_str(front_matter).map_err(|e| invalid(&e)),
+        // an empty YAML document is null rather than an empty mapping
+        Format::Yaml if front_matter.trim().is_empty() => Ok(toml::Table::new()),
+        Format::Yaml => serde_yaml_ng::from_str(front_matter).map_err(|e| invalid(&e)),
+    }
+}
+
+fn invalid(e: &dyn std::fmt::Display) -> String {
+    // the error names the offending key on a second line
+    let e = e.to_string().trim().replace('\n', " ");
+    format!("Invalid front matter in template: {e}")
+}
+
+/// The language of the front matter and the rest of the template, when it starts with a `---`
+/// or `+++` line
+fn strip_opening_line(template: &str) -> Option<(Format, &str)> {
+    [Format::Yaml, Format::Toml].into_iter().find_map(|format| {
+        let rest = template.strip_prefix(format.delimiter())?;
+        let rest = rest.strip_prefix('\r').unwrap_or(rest);
+        Some((format, rest.strip_prefix('\n')?))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_settings_from_template() {
+        let template = "+++\nstrict = true\noutput = \"README.md\"\n+++\n\n# {{crate}}\n";
+        let (config, body) = split_front_matter(template).unwrap();
+        assert_eq!(Some(true), config.strict);
+        assert_eq!(Some("README.md"), config.output.as_deref());
+        assert_eq!("# {{crate}}\n", body);
+    }
+
+    #[test]
+    fn split_yaml_settings_from_template() {
+        let template = "---\nstrict: true\nchangelog-entries: 3\nvars:\n  audience: users\n---\n\n\
+                        # {{crate}}\n";
+        let (config, body) = split_front_matter(template).unwrap();
+        assert_eq!(Some(true), config.strict);
+        assert_eq!(Some(3), config.changelog_entries);
+        assert_eq!(
+            Some("users"),
+            config.vars.get("audience").map(String::as_str)
+        );
+        assert_eq!("# {{crate}}\n", body);
+    }
+
+    #[test]
+    fn badge_style_in_front_matter() {
+        let template = "+++\n[badge-style]\nstyle = \"flat-square\"\n+++\n{{badges}}";
+        let (config, _) = split_front_matter(template).unwrap();
+        assert_eq!(Some("flat-square"), config.badge_style.style.as_deref());
+    }
+
+    #[test]
+    fn template_without_front_matter() {
+        let template = "# {{crate}}\n\n---\n\n{{readme}}";
+        let (config, body) = split_front_matter(template).unwrap();
+        assert_eq!(None, config.strict);
+        assert_eq!(template, body);
+    }
+
+    #[test]
+    fn unterminated_front_matter_should_fail() {
+        assert_eq!(
+            "Unterminated front matter in template: missing the closing `---` line",
+            split_front_matter("---\nstrict: true\n{{readme}}").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn template_key_should_fail() {
+        assert_eq!(
+            "Invalid front matter in template: `template` cannot be set by the template itself",
+            split_front_matter("+++\ntemplate = \"OTHER.tpl\"\n+++\n{{readme}}").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn unknown_key_should_fail() {
+        let err = split_front_matter("---\nstrikt: true\n---\n{{readme}}").unwrap_err();
+        assert!(
+            err.starts_with("Invalid front matter in template: unknown field `strikt`"),
+            "{err}"
+        );
+    }
+}
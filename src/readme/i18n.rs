@@ -0,0 +1,320 @@
+//! Localized READMEs
+//!
+//! The translation of the docs for a locale lives in `docs/i18n/<locale>.md`, as markdown split
+//! in sections by its headings. A section replaces the section of the docs whose heading has the
+//! same anchor (`## Examples` is `examples`): either the anchor of the translated heading itself,
+//! or the one given explicitly after it, as in `## 示例 {#examples}`. Text before the first
+//! heading translates the introduction. Sections without a translation are left in the source
+//! language.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::process;
+
+const TRANSLATIONS_DIR: &str = "docs/i18n";
+
+/// The fixed strings cargo-readme adds to the output
+#[derive(Debug)]
+pub struct Strings {
+    /// Start of the license line, as in `License: MIT`.
+    pub license: &'static str,
+    /// Heading of the generated license section.
+    pub license_heading: &'static str,
+    /// Heading of the installation section.
+    pub installation: &'static str,
+    /// Introduction of the `[dependencies]` snippet.
+    pub add_dependency: &'static str,
+    /// Text of the docs.rs link.
+    pub documentation: &'static str,
+}
+
+pub const ENGLISH: Strings = Strings {
+    license: "License: ",
+    license_heading: "License",
+    installation: "Installation",
+    add_dependency: "Add this to your `Cargo.toml`:",
+    documentation: "Documentation",
+};
+
+const CHINESE: Strings = Strings {
+    license: "许可证：",
+    license_heading: "许可证",
+    installation: "安装",
+    add_dependency: "将以下内容添加到你的 `Cargo.toml`：",
+    documentation: "文档",
+};
+
+const JAPANESE: Strings = Strings {
+    license: "ライセンス：",
+    license_heading: "ライセンス",
+    installation: "インストール",
+    add_dependency: "`Cargo.toml` に以下を追加してください：",
+    documentation: "ドキュメント",
+};
+
+/// The fixed strings for `locale`, English when there is no translation
+pub fn strings(locale: Option<&str>) -> &'static Strings {
+    let language = locale
+        .and_then(|locale| locale.split(['-', '_']).next())
+        .map(str::to_lowercase);
+    match language.as_deref() {
+        Some("zh") => &CHINESE,
+        Some("ja") => &JAPANESE,
+        _ => &ENGLISH,
+    }
+}
+
+/// Check that a locale can be used in file names, such as `zh` or `pt-BR`
+pub fn check_locale(locale: &str) -> Result<(), String> {
+    let valid = !locale.is_empty()
+        && locale
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid locale `{locale}`: only letters, digits, `-` and `_` are allowed"
+        ))
+    }
+}
+
+/// Substitute the sections translated for `locale` into the processed docs
+///
+/// A missing translation file only warns, leaving the docs in the source language.
+pub fn translate_docs(
+    docs: Vec<String>,
+    project_root: &Path,
+    locale: &str,
+) -> Result<Vec<String>, String> {
+    check_locale(locale)?;
+
+    let path = Path::new(TRANSLATIONS_DIR).join(format!("{locale}.md"));
+    let text = match std::fs::read_to_string(project_root.join(&path)) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            eprintln!(
+                "Warn: no translation found for locale `{locale}` in {}",
+                path.to_string_lossy()
+            );
+            return Ok(docs);
+        }
+        Err(e) => return Err(format!("Could not read {}: {}", path.to_string_lossy(), e)),
+    };
+
+    let translation = process::process_docs(text.lines().collect::<Vec<_>>(), false);
+    let (result, unused) = substitute(&docs, &translation);
+    for key in unused {
+        eprintln!(
+            "Warn: section `{key}` of {} does not match any heading of the documentation",
+            path.to_string_lossy()
+        );
+    }
+
+    Ok(result)
+}
+
+/// Replace the sections of `docs` that `translation` has, returning the result and the keys of
+/// the translated sections that were not used
+fn substitute(docs: &[String], translation: &[String]) -> (Vec<String>, Vec<String>) {
+    let mut translated: BTreeMap<String, Section> = sections(translation)
+        .into_iter()
+        .filter(|section| section.heading.is_some() || !is_blank(&section.body))
+        .map(|section| (section.key(), section))
+        .collect();
+
+    let mut result = Vec::with_capacity(docs.len());
+    for section in sections(docs) {
+        match translated.remove(&section.key()) {
+            Some(translation) => result.extend(section.replaced_by(translation)),
+            None => result.extend(section.lines()),
+        }
+    }
+
+    (result, translated.into_keys().collect())
+}
+
+/// A heading and the lines up to the next one
+struct Section {
+    /// Level and text of the heading, `None` for the introduction.
+    heading: Option<(usize, String)>,
+    /// Anchor given explicitly with `{#anchor}` after the heading.
+    anchor: Option<String>,
+    body: Vec<String>,
+}
+
+impl Section {
+    /// The anchor the section is matched with, empty for the introduction
+    fn key(&self) -> String {
+        match (&self.anchor, &self.heading) {
+            (Some(anchor), _) => anchor.clone(),
+            (None, Some((_, text))) => slug(text),
+            (None, None) => String::new(),
+        }
+    }
+
+    fn lines(self) -> Vec<String> {
+        let heading = self
+            .heading
+            .map(|(level, text)| format!("{} {}", "#".repeat(level), text));
+        heading.into_iter().chain(self.body).collect()
+    }
+
+    /// The translation, at the heading level and with the surrounding blank lines of this section
+    fn replaced_by(self, translation: Section) -> Vec<String> {
+        let leading = self.body.iter().take_while(|l| l.trim().is_empty()).count();
+        let trailing = if is_blank(&self.body) {
+            // an introduction translated where the docs have none still needs its blank line
+            usize::from(self.heading.is_none())
+        } else {
+            self.body
+                .iter()
+                .rev()
+                .take_while(|l| l.trim().is_empty())
+                .count()
+        };
+
+        let body = trim_blank(&translation.body);
+        let heading = match (self.heading, translation.heading) {
+            (Some((level, _)), Some((_, text))) => Some((level, text)),
+            (heading, _) => heading,
+        };
+
+        Section {
+            heading,
+            anchor: None,
+            body: std::iter::repeat(String::new())
+                .take(leading)
+                .chain(body.iter().cloned())
+                .chain(std::iter::repeat(String::new()).take(trailing))
+                .collect(),
+        }
+        .lines()
+    }
+}
+
+/// Split docs in sections at their headings, leaving code blocks alone
+fn sections(lines: &[String]) -> Vec<Section> {
+    let mut sections = vec![Section {
+        heading: None,
+        anchor: None,
+        body: Vec::new(),
+    }];
+    let mut in_code = false;
+
+    for line in lines {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+        }
+        match heading(line).filter(|_| !in_code) {
+            Some((level, text)) => {
+                let (text, anchor) = split_anchor(text);
+                sections.push(Section {
+                    heading: Some((level, text.to_owned())),
+                    anchor: anchor.map(str::to_owned),
+                    body: Vec::new(),
+                });
+            }
+            None => sections.last_mut().unwrap().body.push(line.clone()),
+        }
+    }
+
+    sections
+}
+
+/// Level and text of a markdown ATX heading
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let text = line[level..].strip_prefix(' ')?;
+    (1..=6).contains(&level).then_some((level, text.trim()))
+}
+
+/// Split an explicit anchor from a heading: `示例 {#examples}`
+fn split_anchor(text: &str) -> (&str, Option<&str>) {
+    if let Some(rest) = text.strip_suffix('}') {
+        if let Some((text, anchor)) = rest.rsplit_once("{#") {
+            return (text.trim_end(), Some(anchor.trim()));
+        }
+    }
+    (text, None)
+}
+
+/// The anchor GitHub gives a heading: lowercase, punctuation removed and spaces as `-`
+fn slug(text: &str) -> String {
+    text.chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn is_blank(lines: &[String]) -> bool {
+    lines.iter().all(|l| l.trim().is_empty())
+}
+
+fn trim_blank(lines: &[String]) -> &[String] {
+    let start = lines
+        .iter()
+        .position(|l| !l.trim().is_empty())
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|l| !l.trim().is_empty())
+        .map_or(start, |i| i + 1);
+    &lines[start..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_owned).collect()
+    }
+
+    #[test]
+    fn heading_slug() {
+        assert_eq!("examples", slug("Examples"));
+        assert_eq!("how-to-use-it", slug("How to use it?"));
+        assert_eq!("使用方法", slug("使用方法"));
+    }
+
+    #[test]
+    fn heading_with_anchor() {
+        assert_eq!(("示例", Some("examples")), split_anchor("示例 {#examples}"));
+        assert_eq!(("Examples", None), split_anchor("Examples"));
+    }
+
+    #[test]
+    fn replace_translated_sections() {
+        let docs = lines("Intro\n\n## Usage\n\nUse it.\n\n## Examples\n\nAn example.");
+        let translation = lines("介绍\n\n# 用法 {#usage}\n\n使用它。\n\n# FAQ\n\nNone.");
+
+        let (result, unused) = substitute(&docs, &translation);
+        assert_eq!(
+            lines("介绍\n\n## 用法\n\n使用它。\n\n## Examples\n\nAn example."),
+            result
+        );
+        assert_eq!(vec!["faq".to_owned()], unused);
+    }
+
+    #[test]
+    fn fixed_strings_by_language() {
+        assert_eq!("许可证：", strings(Some("zh-CN")).license);
+        assert_eq!("ライセンス：", strings(Some("ja")).license);
+        assert_eq!("License: ", strings(Some("fr")).license);
+        assert_eq!("License: ", strings(None).license);
+    }
+
+    #[test]
+    fn locale_with_path_should_fail() {
+        assert!(check_locale("zh").is_ok());
+        assert!(check_locale("pt_BR").is_ok());
+        assert!(check_locale("../zh").is_err());
+    }
+}
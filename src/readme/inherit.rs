@@ -0,0 +1,229 @@
+//! Template inheritance
+//!
+//! A template can start from another one with `{{extends "path"}}`, then override the regions
+//! its parent marks with `{{#block name}}...{{/block}}`. In the parent, the content of a block is
+//! the default used when no child overrides it. Whatever a child has outside its blocks is
+//! ignored. Parents can extend further templates; blocks cannot be nested. The front matter of
+//! each template is merged into its parent's, the child's settings winning.
+
+use regex::{Captures, Regex};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use super::front_matter;
+use crate::suggest;
+
+// `{{extends "path"}}`, capturing the path.
+static RE_EXTENDS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\{\{\s*extends\s+"(?P<path>[^"]*)"\s*\}\}"#).unwrap());
+
+// `{{#block name}}...{{/block}}`, capturing the name and the content.
+static RE_BLOCK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?s)\{\{#block\s+(?P<name>[A-Za-z0-9_-]+)\s*\}\}(?P<content>.*?)\{\{/block\}\}")
+        .unwrap()
+});
+
+/// Follow the `{{extends}}` chain of `template` and fill its blocks
+///
+/// Paths in `{{extends}}` are relative to `dir`, the directory of the template, and then to the
+/// directory of each parent in turn. The result has no `{{extends}}` or block markers left, and
+/// starts with the merged front matter of the chain, if any.
+pub fn resolve_template(template: &str, dir: &Path) -> Result<String, String> {
+    assemble(
+        template,
+        None,
+        dir,
+        BTreeMap::new(),
+        toml::Table::new(),
+        &mut Vec::new(),
+    )
+}
+
+/// Apply `overrides` to `template`, or pass them on to its parent
+fn assemble(
+    template: &str,
+    path: Option<&Path>,
+    dir: &Path,
+    overrides: BTreeMap<String, String>,
+    settings: toml::Table,
+    visited: &mut Vec<PathBuf>,
+) -> Result<String, String> {
+    let (front_matter, template) = front_matter::split_table(template)?;
+    let mut merged_settings = front_matter.unwrap_or_default();
+    merge_settings(&mut merged_settings, settings);
+
+    let blocks = blocks(template);
+
+    let mut parents = RE_EXTENDS.captures_iter(template);
+    let Some(extends) = parents.next() else {
+        let result = fill_blocks(template, path, &blocks, &overrides)?;
+        if merged_settings.is_empty() {
+            return Ok(result);
+        }
+        let front_matter = toml::to_string(&merged_settings).map_err(|e| e.to_string())?;
+        return Ok(format!("+++\n{front_matter}+++\n\n{result}"));
+    };
+    if parents.next().is_some() {
+        return Err(format!(
+            "Only one `{{{{extends}}}}` is allowed, but {} has more",
+            describe(path)
+        ));
+    }
+
+    let parent_path = dir.join(&extends["path"]);
+    let canonical = parent_path.canonicalize().unwrap_or(parent_path.clone());
+    if visited.contains(&canonical) {
+        return Err(format!(
+            "Template '{}' extends itself",
+            parent_path.to_string_lossy()
+        ));
+    }
+    visited.push(canonical);
+
+    let parent = std::fs::read_to_string(&parent_path).map_err(|e| {
+        format!(
+            "Could not open template file '{}': {}",
+            parent_path.to_string_lossy(),
+            e
+        )
+    })?;
+
+    // the blocks of the most derived template win
+    let mut merged = blocks;
+    merged.extend(overrides);
+
+    let parent_dir = parent_path.parent().unwrap_or(dir);
+    assemble(
+        &parent,
+        Some(&parent_path),
+        parent_dir,
+        merged,
+        merged_settings,
+        visited,
+    )
+}
+
+/// Merge the front matter of a child template into its parent's, table by table
+fn merge_settings(parent: &mut toml::Table, child: toml::Table) {
+    for (key, value) in child {
+        match (parent.get_mut(&key), value) {
+            (Some(toml::Value::Table(parent)), toml::Value::Table(child)) => {
+                merge_settings(parent, child)
+            }
+            (_, value) => {
+                parent.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Replace every block with its override, or with its own content
+fn fill_blocks(
+    template: &str,
+    path: Option<&Path>,
+    blocks: &BTreeMap<String, String>,
+    overrides: &BTreeMap<String, String>,
+) -> Result<String, String> {
+    if let Some(name) = overrides.keys().find(|name| !blocks.contains_key(*name)) {
+        let mut msg = format!("Block `{}` is not defined in {}", name, describe(path));
+        if let Some(known) = suggest::did_you_mean(name, blocks.keys().map(String::as_str)) {
+            msg.push_str(&format!(" (did you mean `{known}`?)"));
+        }
+        return Err(msg);
+    }
+
+    let result = RE_BLOCK.replace_all(template, |cap: &Captures| {
+        overrides
+            .get(&cap["name"])
+            .cloned()
+            .unwrap_or_else(|| block_content(&cap["content"]).to_owned())
+    });
+    Ok(result.into_owned())
+}
+
+/// The blocks of a template, by name
+fn blocks(template: &str) -> BTreeMap<String, String> {
+    RE_BLOCK
+        .captures_iter(template)
+        .map(|cap| {
+            (
+                cap["name"].to_owned(),
+                block_content(&cap["content"]).to_owned(),
+            )
+        })
+        .collect()
+}
+
+/// The content of a block, without the line breaks right after its opening tag and before its
+/// closing tag, so blocks can sit on lines of their own
+fn block_content(content: &str) -> &str {
+    let content = content.strip_prefix('\n').unwrap_or(content);
+    content.strip_suffix('\n').unwrap_or(content)
+}
+
+fn describe(path: Option<&Path>) -> String {
+    match path {
+        Some(path) => format!("template '{}'", path.to_string_lossy()),
+        None => "the template".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template_without_extends_keeps_block_content() {
+        let template = "# {{crate}}\n\n{{#block body}}\n{{readme}}\n{{/block}}\n";
+        assert_eq!(
+            "# {{crate}}\n\n{{readme}}\n",
+            resolve_template(template, Path::new(".")).unwrap()
+        );
+    }
+
+    #[test]
+    fn fill_blocks_with_overrides() {
+        let parent =
+            "{{#block header}}# {{crate}}{{/block}}\n\n{{#block body}}{{readme}}{{/block}}";
+        let mut overrides = BTreeMap::new();
+        overrides.insert("body".to_owned(), "{{readme}}\n\nMore".to_owned());
+        assert_eq!(
+            "# {{crate}}\n\n{{readme}}\n\nMore",
+            fill_blocks(parent, None, &blocks(parent), &overrides).unwrap()
+        );
+    }
+
+    #[test]
+    fn override_of_unknown_block_should_fail() {
+        let parent = "{{#block body}}{{readme}}{{/block}}";
+        let mut overrides = BTreeMap::new();
+        overrides.insert("bdy".to_owned(), String::new());
+        assert_eq!(
+            "Block `bdy` is not defined in the template (did you mean `body`?)",
+            fill_blocks(parent, None, &blocks(parent), &overrides).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn merge_front_matter() {
+        let mut parent: toml::Table =
+            toml::from_str("strict = true\noutput = \"README.md\"\n[vars]\na = \"1\"").unwrap();
+        let child: toml::Table =
+            toml::from_str("output = \"OTHER.md\"\n[vars]\nb = \"2\"").unwrap();
+        merge_settings(&mut parent, child);
+        assert_eq!(
+            "output = \"OTHER.md\"\nstrict = true\n\n[vars]\na = \"1\"\nb = \"2\"\n",
+            toml::to_string(&parent).unwrap()
+        );
+    }
+
+    #[test]
+    fn extends_more_than_once_should_fail() {
+        let template = "{{extends \"a.tpl\"}}\n{{extends \"b.tpl\"}}";
+        assert_eq!(
+            "Only one `{{extends}}` is allowed, but the template has more",
+            resolve_template(template, Path::new(".")).unwrap_err()
+        );
+    }
+}
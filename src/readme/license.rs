@@ -0,0 +1,344 @@
+//! Render a license section from the SPDX expression in `Cargo.toml`
+//!
+//! The expression is parsed into its `AND`/`OR`/`WITH` structure so dual licenses get the usual
+//! "at your option" wording. Each license links to a matching `LICENSE-*` (or, for a single
+//! license, `LICENSE`) file in the project root when there is one, otherwise to its SPDX page.
+
+use std::path::Path;
+
+/// A parsed SPDX license expression
+#[derive(Debug, PartialEq)]
+enum Expr {
+    License { id: String, or_later: bool },
+    With(Box<Expr>, String),
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+}
+
+/// Render the license section for `license`, checking for license files in `project_root`
+pub fn license_section(license: &str, project_root: &Path) -> Result<String, String> {
+    let expr = parse(license).map_err(|e| format!("Could not parse license `{license}`: {e}"))?;
+    let single = matches!(expr, Expr::License { .. } | Expr::With(..));
+    let item = |expr: &Expr| render_item(expr, project_root, single);
+
+    let section = match &expr {
+        Expr::Or(alternatives) => format!(
+            "Licensed under either of\n\n{}\n\nat your option.",
+            render_list(alternatives, item)
+        ),
+        Expr::And(licenses) => format!(
+            "Licensed under all of the following:\n\n{}",
+            render_list(licenses, item)
+        ),
+        _ => format!("Licensed under the {}.", item(&expr)),
+    };
+    Ok(section)
+}
+
+fn render_list<F: Fn(&Expr) -> String>(exprs: &[Expr], item: F) -> String {
+    exprs
+        .iter()
+        .map(|expr| format!("* {}", item(expr)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render one license, linked to its license file or SPDX page
+fn render_item(expr: &Expr, project_root: &Path, single: bool) -> String {
+    match expr {
+        Expr::License { id, or_later } => {
+            let mut name = license_name(id);
+            if *or_later {
+                name.push_str(" or later");
+            }
+            match find_license_file(id, project_root, single) {
+                Some(file) => format!("{name} ([{file}]({file}) or {})", spdx_url(id)),
+                None => format!("[{name}]({})", spdx_url(id)),
+            }
+        }
+        Expr::With(license, exception) => format!(
+            "{} with the [{exception}]({})",
+            render_item(license, project_root, single),
+            spdx_url(exception)
+        ),
+        Expr::And(licenses) => format!(
+            "all of {}",
+            join_inline(licenses, "and", project_root, single)
+        ),
+        Expr::Or(alternatives) => format!(
+            "either {}",
+            join_inline(alternatives, "or", project_root, single)
+        ),
+    }
+}
+
+/// Join licenses in a sentence: "a, b and c"
+fn join_inline(exprs: &[Expr], conjunction: &str, project_root: &Path, single: bool) -> String {
+    let items: Vec<String> = exprs
+        .iter()
+        .map(|expr| render_item(expr, project_root, single))
+        .collect();
+    match items.split_last() {
+        Some((last, rest)) if !rest.is_empty() => {
+            format!("{} {conjunction} {last}", rest.join(", "))
+        }
+        _ => items.concat(),
+    }
+}
+
+fn spdx_url(id: &str) -> String {
+    format!("https://spdx.org/licenses/{id}.html")
+}
+
+/// Human readable name of the common licenses, the SPDX id for the others
+fn license_name(id: &str) -> String {
+    let base = id.trim_end_matches("-only").trim_end_matches("-or-later");
+    let name = match base {
+        "MIT" => "MIT license",
+        "Apache-2.0" => "Apache License, Version 2.0",
+        "BSD-2-Clause" => "BSD 2-Clause License",
+        "BSD-3-Clause" => "BSD 3-Clause License",
+        "0BSD" => "BSD Zero Clause License",
+        "ISC" => "ISC License",
+        "MPL-2.0" => "Mozilla Public License 2.0",
+        "GPL-2.0" => "GNU General Public License v2.0",
+        "GPL-3.0" => "GNU General Public License v3.0",
+        "LGPL-2.1" => "GNU Lesser General Public License v2.1",
+        "LGPL-3.0" => "GNU Lesser General Public License v3.0",
+        "AGPL-3.0" => "GNU Affero General Public License v3.0",
+        "Unlicense" => "Unlicense",
+        "Zlib" => "zlib License",
+        "BSL-1.0" => "Boost Software License 1.0",
+        "CC0-1.0" => "Creative Commons Zero v1.0 Universal",
+        "Unicode-3.0" => "Unicode License v3",
+        _ => return id.to_owned(),
+    };
+
+    let mut name = name.to_owned();
+    if id.ends_with("-or-later") {
+        name.push_str(" or later");
+    }
+    name
+}
+
+/// Find the license file for `id` in the project root
+///
+/// Looks for `LICENSE-<id>` and `LICENSE-<NAME>` (e.g. `LICENSE-APACHE` for `Apache-2.0`), with
+/// an optional `.md` or `.txt` extension. When the crate has a single license, a plain `LICENSE`
+/// or `COPYING` file counts too.
+fn find_license_file(id: &str, project_root: &Path, single: bool) -> Option<String> {
+    let short = id.split('-').next().unwrap_or(id).to_uppercase();
+    let mut candidates: Vec<String> = [id, &short]
+        .iter()
+        .flat_map(|name| ["", ".md", ".txt"].map(|ext| format!("LICENSE-{name}{ext}")))
+        .collect();
+    if single {
+        candidates.extend(["LICENSE", "LICENSE.md", "LICENSE.txt", "COPYING"].map(String::from));
+    }
+
+    candidates
+        .into_iter()
+        .find(|file| project_root.join(file).is_file())
+}
+
+/// Parse an SPDX license expression
+///
+/// `OR` binds looser than `AND`, which binds looser than `WITH`. The `/` separator of old
+/// `Cargo.toml` files is read as `OR`.
+fn parse(expr: &str) -> Result<Expr, String> {
+    let tokens = tokenize(expr);
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.parse_or()?;
+    match parser.tokens.get(parser.pos) {
+        None => Ok(expr),
+        Some(token) => Err(format!("unexpected `{token}`")),
+    }
+}
+
+fn tokenize(expr: &str) -> Vec<String> {
+    expr.replace('(', " ( ")
+        .replace(')', " ) ")
+        .replace('/', " OR ")
+        .split_whitespace()
+        .map(String::from)
+        .collect()
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn next(&mut self) -> Result<&str, String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .ok_or_else(|| "unexpected end of expression".to_owned())?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    /// Parse operands separated by `op`, with `operand` parsing each of them
+    fn parse_list(
+        &mut self,
+        op: &str,
+        operand: fn(&mut Self) -> Result<Expr, String>,
+    ) -> Result<Vec<Expr>, String> {
+        let mut operands = vec![operand(self)?];
+        while self.peek().is_some_and(|t| t.eq_ignore_ascii_case(op)) {
+            self.pos += 1;
+            operands.push(operand(self)?);
+        }
+        Ok(operands)
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, String> {
+        let mut alternatives = self.parse_list("OR", Self::parse_and)?;
+        Ok(match alternatives.len() {
+            1 => alternatives.remove(0),
+            _ => Expr::Or(alternatives),
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut licenses = self.parse_list("AND", Self::parse_with)?;
+        Ok(match licenses.len() {
+            1 => licenses.remove(0),
+            _ => Expr::And(licenses),
+        })
+    }
+
+    fn parse_with(&mut self) -> Result<Expr, String> {
+        let license = self.parse_primary()?;
+        if self.peek().is_some_and(|t| t.eq_ignore_ascii_case("WITH")) {
+            self.pos += 1;
+            let exception = self.next()?.to_owned();
+            return Ok(Expr::With(Box::new(license), exception));
+        }
+        Ok(license)
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, String> {
+        match self.next()? {
+            "(" => {
+                let expr = self.parse_or()?;
+                match self.next()? {
+                    ")" => Ok(expr),
+                    token => Err(format!("expected `)`, found `{token}`")),
+                }
+            }
+            token
+                if token == ")"
+                    || ["AND", "OR", "WITH"]
+                        .iter()
+                        .any(|op| token.eq_ignore_ascii_case(op)) =>
+            {
+                Err(format!("unexpected `{token}`"))
+            }
+            token => {
+                let (id, or_later) = match token.strip_suffix('+') {
+                    Some(id) => (id.to_owned(), true),
+                    None => (token.to_owned(), false),
+                };
+                Ok(Expr::License { id, or_later })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn license(id: &str) -> Expr {
+        Expr::License {
+            id: id.to_owned(),
+            or_later: false,
+        }
+    }
+
+    #[test]
+    fn parse_precedence() {
+        assert_eq!(
+            Expr::Or(vec![
+                license("MIT"),
+                Expr::And(vec![
+                    license("Apache-2.0"),
+                    Expr::With(
+                        Box::new(license("GPL-2.0")),
+                        "Classpath-exception-2.0".into()
+                    ),
+                ]),
+            ]),
+            parse("MIT OR Apache-2.0 AND GPL-2.0 WITH Classpath-exception-2.0").unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_parentheses_and_slash() {
+        assert_eq!(
+            Expr::And(vec![
+                Expr::Or(vec![license("MIT"), license("Apache-2.0")]),
+                license("Unicode-3.0"),
+            ]),
+            parse("(MIT/Apache-2.0) AND Unicode-3.0").unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Err("expected `)`, found `Apache-2.0`".into()),
+            parse("(MIT Apache-2.0)")
+        );
+        assert_eq!(Err("unexpected end of expression".into()), parse("MIT OR"));
+        assert_eq!(Err("unexpected `OR`".into()), parse("MIT OR OR Apache-2.0"));
+        assert_eq!(Err("unexpected `)`".into()), parse("MIT)"));
+    }
+
+    #[test]
+    fn dual_license_with_local_files() {
+        // this repository ships LICENSE-MIT and LICENSE-APACHE
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        assert_eq!(
+            "Licensed under either of\n\n\
+             * MIT license ([LICENSE-MIT](LICENSE-MIT) or https://spdx.org/licenses/MIT.html)\n\
+             * Apache License, Version 2.0 ([LICENSE-APACHE](LICENSE-APACHE) or \
+             https://spdx.org/licenses/Apache-2.0.html)\n\n\
+             at your option.",
+            license_section("MIT OR Apache-2.0", root).unwrap()
+        );
+    }
+
+    #[test]
+    fn single_license_without_local_file() {
+        let root = Path::new("does-not-exist");
+        assert_eq!(
+            "Licensed under the [GNU General Public License v3.0 or later]\
+             (https://spdx.org/licenses/GPL-3.0-or-later.html).",
+            license_section("GPL-3.0-or-later", root).unwrap()
+        );
+        assert_eq!(
+            "Licensed under the [Apache License, Version 2.0](https://spdx.org/licenses/Apache-2.0.html) \
+             with the [LLVM-exception](https://spdx.org/licenses/LLVM-exception.html).",
+            license_section("Apache-2.0 WITH LLVM-exception", root).unwrap()
+        );
+    }
+
+    #[test]
+    fn nested_expression() {
+        let root = Path::new("does-not-exist");
+        assert_eq!(
+            "Licensed under all of the following:\n\n\
+             * either [MIT license](https://spdx.org/licenses/MIT.html) or \
+             [Apache License, Version 2.0](https://spdx.org/licenses/Apache-2.0.html)\n\
+             * [Unicode License v3](https://spdx.org/licenses/Unicode-3.0.html)",
+            license_section("(MIT OR Apache-2.0) AND Unicode-3.0", root).unwrap()
+        );
+    }
+}
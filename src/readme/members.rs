@@ -0,0 +1,201 @@
+//! Workspace index
+//!
+//! `{{#each members}}...{{/each}}` repeats its content for every member of the workspace, with
+//! `{{name}}`, `{{version}}`, `{{description}}`, `{{summary}}`, `{{badges}}` and `{{path}}` set to
+//! the member's. When the tags sit on lines of their own, those lines are left out of the output,
+//! so a loop can produce the rows of a table.
+
+use regex::{Captures, Regex};
+use std::fs::File;
+use std::path::{Component, Path};
+use std::sync::LazyLock;
+
+use super::{process, read_docs, summary};
+use crate::config::{self, project, BadgeRegistry, BadgeStyle, GitInfo};
+
+// `{{#each members}}...{{/each}}`, capturing the line break after the opening tag, the content
+// and the line break after the closing tag.
+static RE_EACH_MEMBERS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?s)\{\{#each\s+members\s*\}\}(?P<open>\n?)(?P<body>.*?)\{\{/each\}\}(?P<close>\n?)",
+    )
+    .unwrap()
+});
+
+/// Variables set inside a `{{#each members}}` loop
+pub const MEMBER_VARIABLES: &[&str] = &[
+    "name",
+    "version",
+    "description",
+    "summary",
+    "badges",
+    "path",
+];
+
+/// What the index shows of a workspace member
+#[derive(Debug, Default, Clone)]
+pub struct Member {
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    /// First paragraph of the member's documentation.
+    pub summary: String,
+    /// The member's badges, on a single line so they fit in a table cell.
+    pub badges: String,
+    /// Directory of the member relative to the workspace root, with `/` separators.
+    pub path: String,
+}
+
+/// Does the template loop over the workspace members?
+pub fn uses_members(template: &str) -> bool {
+    RE_EACH_MEMBERS.is_match(template)
+}
+
+/// The byte ranges of the loops in the template, tags included
+pub fn loops(template: &str) -> Vec<std::ops::Range<usize>> {
+    RE_EACH_MEMBERS
+        .find_iter(template)
+        .map(|m| m.range())
+        .collect()
+}
+
+/// Read the members of the workspace the project belongs to, their badges styled with `style`
+/// over their own `badge-style` table
+///
+/// The members share `git`, the repository of the workspace.
+pub fn members(
+    project_root: &Path,
+    badges: &BadgeRegistry,
+    style: &BadgeStyle,
+    git: Option<&GitInfo>,
+) -> Result<Vec<Member>, String> {
+    let workspace_root = project::find_workspace_root(project_root, None).ok_or_else(|| {
+        "`{{#each members}}` was found in template but the project is not in a workspace".to_owned()
+    })?;
+
+    project::workspace_members(&workspace_root)?
+        .iter()
+        .map(|dir| member(&workspace_root, dir, badges, style, git))
+        .collect()
+}
+
+fn member(
+    workspace_root: &Path,
+    dir: &Path,
+    badges: &BadgeRegistry,
+    style: &BadgeStyle,
+    git: Option<&GitInfo>,
+) -> Result<Member, String> {
+    let mut cargo = config::read_manifest(dir)?;
+    cargo.render_badges(badges, style, git)?;
+
+    let badges: Vec<&str> = cargo.badges.iter().map(AsRef::as_ref).collect();
+    let path: Vec<String> = dir
+        .strip_prefix(workspace_root)
+        .unwrap_or(dir)
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(c) => Some(c.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+    let path = if path.is_empty() {
+        ".".to_owned()
+    } else {
+        path.join("/")
+    };
+
+    Ok(Member {
+        summary: member_summary(dir, &cargo)?.unwrap_or_default(),
+        name: cargo.name,
+        version: cargo.version,
+        description: cargo.description.unwrap_or_default(),
+        badges: badges.join(" "),
+        path,
+    })
+}
+
+/// The summary of a member's docs, read from the same file `cargo readme` would use for it
+fn member_summary(dir: &Path, cargo: &config::Manifest) -> Result<Option<String>, String> {
+    let source = match cargo.config.input.as_deref() {
+        Some(input) => dir.join(input),
+        None => match project::find_entrypoint(dir, cargo) {
+            Ok(entrypoint) => dir.join(entrypoint),
+            // a member without docs has no summary
+            Err(_) => return Ok(None),
+        },
+    };
+    let mut source = File::open(&source)
+        .map_err(|e| format!("Could not open file '{}': {}", source.to_string_lossy(), e))?;
+
+    let extract_from_comment = !cargo.config.no_comment_extraction.unwrap_or(false);
+    let lines = read_docs(&mut source, extract_from_comment)?;
+    Ok(summary::summary(&process::process_docs(lines, false)))
+}
+
+/// Repeat the content of every `{{#each members}}` loop for each member
+pub fn expand_members(template: &str, members: &[Member]) -> String {
+    RE_EACH_MEMBERS
+        .replace_all(template, |cap: &Captures| {
+            let body = &cap["body"];
+            let mut result: String = members.iter().map(|m| fill(body, m)).collect();
+            // a closing tag on a line of its own takes its line break along
+            let standalone = body.is_empty() || body.ends_with('\n');
+            if !standalone {
+                result.push_str(&cap["close"]);
+            }
+            result
+        })
+        .into_owned()
+}
+
+fn fill(body: &str, member: &Member) -> String {
+    let values = [
+        ("name", &member.name),
+        ("version", &member.version),
+        ("description", &member.description),
+        ("summary", &member.summary),
+        ("badges", &member.badges),
+        ("path", &member.path),
+    ];
+    values.iter().fold(body.to_owned(), |body, (name, value)| {
+        body.replace(&format!("{{{{{name}}}}}"), value)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn members() -> Vec<Member> {
+        ["core", "cli"]
+            .iter()
+            .map(|name| Member {
+                name: name.to_string(),
+                version: "1.0.0".to_owned(),
+                path: format!("crates/{name}"),
+                ..Member::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn expand_table_rows() {
+        let template = "| Crate | Version |\n|---|---|\n{{#each members}}\n\
+                        | [{{name}}]({{path}}) | {{version}} |\n{{/each}}\n\n{{readme}}";
+        assert_eq!(
+            "| Crate | Version |\n|---|---|\n| [core](crates/core) | 1.0.0 |\n\
+             | [cli](crates/cli) | 1.0.0 |\n\n{{readme}}",
+            expand_members(template, &members())
+        );
+    }
+
+    #[test]
+    fn expand_inline() {
+        let template = "Crates: {{#each members}}{{name}} {{/each}}\n{{crate}}";
+        assert_eq!(
+            "Crates: core cli \n{{crate}}",
+            expand_members(template, &members())
+        );
+    }
+}
@@ -1,15 +1,108 @@
+use std::collections::BTreeMap;
 use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
 
+mod badge_layout;
+mod builtin;
+mod changelog;
+mod exec;
 mod extract;
+mod front_matter;
+mod i18n;
+mod inherit;
+mod license;
+mod members;
 mod process;
+mod summary;
 mod template;
 
-use crate::config;
+use crate::config::{self, BadgeRegistry, BadgeStyle, Manifest};
+
+pub use self::badge_layout::{BadgeLayout, BADGE_LAYOUTS};
+pub use self::builtin::{builtin_template, BuiltinTemplate, BUILTIN_TEMPLATES};
+pub use self::front_matter::split_front_matter;
+pub use self::i18n::check_locale;
+pub use self::inherit::resolve_template;
+
+/// The templates bundled with cargo-readme, in listing order.
+pub fn builtin_templates() -> &'static [BuiltinTemplate] {
+    BUILTIN_TEMPLATES
+}
+
+/// How the output is laid out when no template is used
+///
+/// Only `badge_layout` and `badge_style` also apply to templates.
+#[derive(Debug, Default, Clone)]
+pub struct Layout {
+    /// Prepend the title, the crate name unless `title` is set.
+    pub add_title: bool,
+    /// Prepend the badges defined in `Cargo.toml`.
+    pub add_badges: bool,
+    /// Append the license defined in `Cargo.toml`.
+    pub add_license: bool,
+    /// Append a section generated from the license expression instead of the license line.
+    pub license_section: bool,
+    /// Text of the title, instead of the crate name.
+    pub title: Option<String>,
+    /// Add the package description below the title.
+    pub subtitle: bool,
+    /// Add a link to the documentation on docs.rs below the title.
+    pub docs_link: bool,
+    /// Append an installation section, with `cargo install` for binaries or the `[dependencies]`
+    /// line for libraries.
+    pub install_section: bool,
+    /// Center the badges, title, description and docs link in an HTML block.
+    pub centered_header: bool,
+    /// How the badges are placed, here and by `{{badges}}` in templates.
+    pub badge_layout: BadgeLayout,
+    /// The shields.io parameters of the badges, over the `badge-style` table of `Cargo.toml`,
+    /// such as the ones the front matter of the template sets.
+    pub badge_style: BadgeStyle,
+}
+
+/// How a README is generated from the docs and the template
+#[derive(Debug, Clone)]
+pub struct GenerateOptions {
+    /// How the output is laid out, and how the badges are placed and styled.
+    pub layout: Layout,
+    /// User defined template variables.
+    pub vars: BTreeMap<String, String>,
+    /// Replace the sections of the docs translated in `docs/i18n/<locale>.md`, and translate the
+    /// fixed strings of the layout.
+    pub locale: Option<String>,
+    /// Make `{{changelog}}` list the latest releases instead of the notes of the current version.
+    pub changelog_entries: Option<usize>,
+    /// Add a level to the headings of the docs, so the title is the only `#` heading.
+    pub indent_headings: bool,
+    /// Read the docs from the doc comments of the source instead of taking it as markdown.
+    pub extract_from_comment: bool,
+    /// Let the `{{exec "..."}}` directives of the template run their command.
+    pub allow_exec: bool,
+    /// Only allow known variables in the template, and fail on a missing badge or license it
+    /// refers to and on the mistakes in `[badges]` that are otherwise only warned about.
+    pub strict: bool,
+}
+
+impl Default for GenerateOptions {
+    fn default() -> Self {
+        GenerateOptions {
+            layout: Layout::default(),
+            vars: BTreeMap::new(),
+            locale: None,
+            changelog_entries: None,
+            indent_headings: true,
+            extract_from_comment: true,
+            allow_exec: false,
+            strict: false,
+        }
+    }
+}
 
 /// Generates readme data from `source` file
 ///
-/// Optionally, a template can be used to render the output
+/// Optionally, a template can be used to render the output. Without a template, the title,
+/// badges and license line are added as the flags tell. [`generate_readme_with`] takes the other
+/// options.
 #[allow(clippy::too_many_arguments)]
 pub fn generate_readme<T: Read>(
     project_root: &Path,
@@ -21,16 +114,62 @@ pub fn generate_readme<T: Read>(
     indent_headings: bool,
     extract_from_comment: bool,
 ) -> Result<String, String> {
-    let lines = if extract_from_comment {
-        extract::extract_docs(source).map_err(|e| format!("{}", e))?
-    } else {
-        BufReader::new(source)
-            .lines()
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| format!("{}", e))?
+    let options = GenerateOptions {
+        layout: Layout {
+            add_title,
+            add_badges,
+            add_license,
+            ..Layout::default()
+        },
+        indent_headings,
+        extract_from_comment,
+        ..GenerateOptions::default()
     };
+    let cargo = config::read_manifest(project_root)?;
+    generate_readme_with(
+        project_root,
+        cargo,
+        source,
+        template,
+        &BadgeRegistry::new(),
+        &options,
+    )
+}
+
+/// Generates readme data from `source` file, as `options` tell
+///
+/// Optionally, a template can be used to render the output. Without a template, the output is
+/// laid out as `options.layout` describes. `cargo` is the manifest of the project, read with
+/// [`read_manifest`](crate::read_manifest); its `[badges]` entries are rendered by the providers
+/// of `badges`.
+///
+/// A template that `{{extends}}` another one is resolved relative to the project root; use
+/// [`resolve_template`] first for templates read from elsewhere. The front matter of the template
+/// is left out of the output but its settings are not applied, read them with
+/// [`split_front_matter`].
+///
+/// A `{{#each members}}` loop in the template lists the members of the workspace the project
+/// belongs to; the project can be the root of a virtual workspace, with an empty `source`.
+pub fn generate_readme_with<T: Read, U: Read>(
+    project_root: &Path,
+    mut cargo: Manifest,
+    source: &mut T,
+    template: Option<&mut U>,
+    badges: &BadgeRegistry,
+    options: &GenerateOptions,
+) -> Result<String, String> {
+    let layout = &options.layout;
+    let locale = options.locale.as_deref();
+    let strict = options.strict;
 
-    let readme = process::process_docs(lines, indent_headings).join("\n");
+    let lines = read_docs(source, options.extract_from_comment)?;
+    let docs = process::process_docs(lines, options.indent_headings);
+    let docs = match locale {
+        Some(locale) => i18n::translate_docs(docs, project_root, locale)?,
+        None => docs,
+    };
+    let summary = summary::summary(&docs);
+    let readme = docs.join("\n");
 
     // get template from file
     let template = if let Some(template) = template {
@@ -39,10 +178,174 @@ pub fn generate_readme<T: Read>(
         None
     };
 
-    // get manifest from Cargo.toml
-    let cargo = config::get_manifest(project_root)?;
+    // templates not read from a file extend others relative to the project root
+    let template = template
+        .map(|t| inherit::resolve_template(&t, project_root))
+        .transpose()?;
+
+    // the settings in the front matter are up to the caller, see `split_front_matter`
+    let template = template
+        .map(|t| front_matter::split_front_matter(&t).map(|(_, body)| body.to_owned()))
+        .transpose()?;
+
+    // run the commands the template embeds, their output is put in place after the substitutions
+    let (template, exec_outputs) = match template {
+        Some(t) => {
+            let (template, outputs) = exec::expand_exec(&t, project_root, options.allow_exec)?;
+            (Some(template), outputs)
+        }
+        None => (None, Vec::new()),
+    };
+
+    // the repository is read once, for the defaults of the badges and the `{{git.*}}` variables
+    let uses_members = template.as_deref().is_some_and(members::uses_members);
+    let git = match !cargo.badge_entries.is_empty()
+        || uses_members
+        || uses_git_variables(template.as_deref())
+    {
+        true => config::git_info(project_root),
+        false => None,
+    };
+
+    cargo.render_badges(badges, &layout.badge_style, git.as_ref())?;
+    for warning in &cargo.badge_warnings {
+        template::warn_or_fail(warning, strict)?;
+    }
+
+    // user defined variables, completed by the ones derived from the project
+    template::check_user_variables(&options.vars)?;
+    let mut vars = options.vars.clone();
+
+    if let Some(summary) = summary {
+        vars.insert("summary".to_owned(), summary);
+    }
+
+    let uses_variable = |name: &str| match &template {
+        Some(template) => template.contains(&format!("{{{{{name}}}}}")),
+        None => false,
+    };
+
+    if layout.license_section || uses_variable("license_section") {
+        if let Some(license) = &cargo.license {
+            let section = license::license_section(license, project_root)?;
+            vars.insert("license_section".to_owned(), section);
+        }
+    }
+
+    if let Some(offset) = template.as_deref().and_then(|t| t.find("{{changelog}}")) {
+        let level = changelog::heading_level_before(template.as_deref().unwrap(), offset);
+        let excerpt = changelog::changelog(
+            project_root,
+            &cargo.version,
+            options.changelog_entries,
+            level,
+        );
+        let excerpt = match excerpt {
+            Ok(excerpt) => excerpt,
+            Err(missing) => {
+                let msg = format!("`{{{{changelog}}}}` was found in template but {missing}");
+                template::warn_or_fail(&msg, strict)?;
+                String::new()
+            }
+        };
+        vars.insert("changelog".to_owned(), excerpt);
+    }
+
+    if let Some(git) = git
+        .as_ref()
+        .filter(|_| uses_git_variables(template.as_deref()))
+    {
+        let short_commit = git.commit.as_ref().map(|c| c.chars().take(7).collect());
+        let git_vars = [
+            ("git.commit", git.commit.clone()),
+            ("git.short_commit", short_commit),
+            ("git.tag", git.tag.clone()),
+            ("git.branch", git.branch.clone()),
+            ("git.default_branch", git.default_branch.clone()),
+            ("git.remote_url", git.remote_url.clone()),
+        ];
+        for (name, value) in git_vars {
+            if let Some(value) = value {
+                vars.insert(name.to_owned(), value);
+            }
+        }
+    }
+
+    let members = match uses_members {
+        true => members::members(project_root, badges, &layout.badge_style, git.as_ref())?,
+        false => Vec::new(),
+    };
+
+    let binary = is_binary(project_root, &cargo);
+    let strings = i18n::strings(locale);
+    let readme = template::render(
+        template, readme, &cargo, &vars, &members, layout, badges, strings, binary, strict,
+    )?;
+    Ok(exec::restore_exec(readme, &exec_outputs))
+}
+
+/// Is the project a binary crate, without a library to depend on?
+fn is_binary(project_root: &Path, cargo: &config::Manifest) -> bool {
+    let lib = cargo.lib.is_some() || project_root.join("src/lib.rs").exists();
+    let bin = !cargo.bin.is_empty() || project_root.join("src/main.rs").exists();
+    bin && !lib
+}
+
+/// Compare `package.description`, as read in `cargo`, with the summary of the docs in `source`
+///
+/// The summary is the first paragraph of the docs, also available to templates as `{{summary}}`,
+/// or only its first sentence with `first_sentence`. Returns a message when both match, or when
+/// `sync` rewrote the description in `Cargo.toml` to the summary; drift is reported as an error.
+pub fn check_description<T: Read>(
+    project_root: &Path,
+    cargo: &Manifest,
+    source: &mut T,
+    extract_from_comment: bool,
+    first_sentence: bool,
+    sync: bool,
+) -> Result<String, String> {
+    let lines = read_docs(source, extract_from_comment)?;
+    let docs = process::process_docs(lines, false);
+    let summary = summary::summary(&docs)
+        .ok_or_else(|| "The documentation has no summary paragraph".to_owned())?;
+    let summary = match first_sentence {
+        true => summary::first_sentence(&summary).to_owned(),
+        false => summary,
+    };
+
+    let description = cargo.description.as_deref().unwrap_or("");
+    if summary::normalize(description) == summary::normalize(&summary) {
+        return Ok("`description` in Cargo.toml matches the documentation summary".to_owned());
+    }
+
+    if sync {
+        config::set_description(project_root, &summary::plain_text(&summary))?;
+        return Ok("Updated `description` in Cargo.toml to the documentation summary".to_owned());
+    }
+
+    Err(format!(
+        "`description` in Cargo.toml does not match the documentation summary\n  \
+         description: {}\n  summary:     {}",
+        cargo.description.as_deref().unwrap_or("(none)"),
+        summary
+    ))
+}
+
+/// Read the doc lines from `source`, either from its doc comments or as a plain markdown file
+fn read_docs<T: Read>(source: &mut T, extract_from_comment: bool) -> Result<Vec<String>, String> {
+    if extract_from_comment {
+        extract::extract_docs(source).map_err(|e| format!("{}", e))
+    } else {
+        BufReader::new(source)
+            .lines()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("{}", e))
+    }
+}
 
-    template::render(template, readme, &cargo, add_title, add_badges, add_license)
+/// Does the template use any of the `{{git.*}}` variables?
+fn uses_git_variables(template: Option<&str>) -> bool {
+    template.is_some_and(|t| t.contains("{{git."))
 }
 
 /// Load a template String from a file
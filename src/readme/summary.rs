@@ -0,0 +1,110 @@
+//! Find the summary of the documentation
+//!
+//! Like rustdoc, the summary is the first paragraph of the docs. Leading headings, badges and
+//! code blocks are skipped, and the lines of the paragraph are joined into one.
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+// Inline markdown links, `[text](url)` or `[text][label]`, capturing the text.
+static RE_LINK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]*)\](?:\([^)]*\)|\[[^\]]*\])").unwrap());
+
+/// The first paragraph of the processed docs, as markdown on a single line
+pub fn summary<S: AsRef<str>>(lines: &[S]) -> Option<String> {
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut in_code = false;
+
+    for line in lines.iter().map(|l| l.as_ref().trim()) {
+        let fence = line.starts_with("```") || line.starts_with("~~~");
+        if fence {
+            in_code = !in_code;
+        }
+
+        let skipped = fence
+            || in_code
+            || line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with("[![")
+            || line.starts_with("![");
+        if skipped {
+            if paragraph.is_empty() {
+                continue;
+            }
+            break;
+        }
+        paragraph.push(line);
+    }
+
+    (!paragraph.is_empty()).then(|| paragraph.join(" "))
+}
+
+/// The first sentence of a summary, up to the first `.`, `!` or `?` followed by a space
+pub fn first_sentence(summary: &str) -> &str {
+    summary
+        .char_indices()
+        .find(|&(i, c)| matches!(c, '.' | '!' | '?') && summary[i + 1..].starts_with(' '))
+        .map_or(summary, |(i, _)| &summary[..=i])
+}
+
+/// The summary as plain text, suitable for `package.description`
+pub fn plain_text(summary: &str) -> String {
+    let text = RE_LINK.replace_all(summary, "$1");
+    text.replace('`', "").replace("**", "").replace('*', "")
+}
+
+/// Normalize a description or summary for comparison
+///
+/// Markdown formatting, repeated whitespace and a final period do not count as drift.
+pub fn normalize(text: &str) -> String {
+    let text = plain_text(text);
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    text.trim_end_matches('.').to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_is_first_paragraph() {
+        let lines = [
+            "![Logo](logo.svg)",
+            "",
+            "## Overview",
+            "",
+            "Generate README.md from",
+            "doc comments.",
+            "",
+            "More details.",
+        ];
+        assert_eq!(
+            Some("Generate README.md from doc comments.".to_owned()),
+            summary(&lines)
+        );
+    }
+
+    #[test]
+    fn summary_skips_code_blocks() {
+        let lines = ["```rust", "let a = 1;", "```", "The summary."];
+        assert_eq!(Some("The summary.".to_owned()), summary(&lines));
+        assert_eq!(None, summary(&["", "# Title"]));
+    }
+
+    #[test]
+    fn first_sentence_of_summary() {
+        assert_eq!(
+            "Generate a README.md from `lib.rs`.",
+            first_sentence("Generate a README.md from `lib.rs`. It reads the doc comments.")
+        );
+        assert_eq!("No final period", first_sentence("No final period"));
+    }
+
+    #[test]
+    fn normalize_ignores_formatting() {
+        assert_eq!(
+            normalize("A `cargo` subcommand for [README](https://example.com)  files"),
+            normalize("A cargo subcommand for README files.")
+        );
+    }
+}
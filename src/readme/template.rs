@@ -1,38 +1,158 @@
-use crate::config::Manifest;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use super::badge_layout;
+use super::i18n::Strings;
+use super::members::{self, Member, MEMBER_VARIABLES};
+use super::Layout;
+use crate::config::{BadgeCategory, BadgeRegistry, Manifest, AUTO_BADGES};
+use crate::suggest;
+
+// A `{{...}}` placeholder, capturing whatever is between the braces.
+static RE_PLACEHOLDER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{\{(.*?)\}\}").unwrap());
+
+// `{{badge.<key>}}` or `{{badges.<category>}}`, capturing the prefix and the name.
+static RE_BADGE_PLACEHOLDER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{(badges?)\.([A-Za-z0-9_-]+)\}\}").unwrap());
+
+/// Variables understood by `process_template`
+const TEMPLATE_VARIABLES: &[&str] = &[
+    "readme",
+    "crate",
+    "badges",
+    "license",
+    "version",
+    "license_section",
+    "changelog",
+    "summary",
+    "git.commit",
+    "git.short_commit",
+    "git.tag",
+    "git.branch",
+    "git.default_branch",
+    "git.remote_url",
+];
+
+/// Built-in variables derived from the project and passed along with the user defined ones,
+/// with the reason they can be missing
+const DERIVED_VARIABLES: &[(&str, &str)] = &[
+    ("license_section", "no license was provided"),
+    (
+        "changelog",
+        "`CHANGELOG.md` has no entry for the documented version",
+    ),
+    ("summary", "the documentation has no summary paragraph"),
+    ("git.commit", "the project is not in a git repository"),
+    ("git.short_commit", "the project is not in a git repository"),
+    ("git.tag", "no tag points at the current commit"),
+    ("git.branch", "no branch is checked out"),
+    (
+        "git.default_branch",
+        "the default branch could not be found",
+    ),
+    ("git.remote_url", "the repository has no remote"),
+];
 
 /// Renders the template
 ///
-/// This is not a real template engine, it just processes a few substitutions.
+/// This is not a real template engine, it just processes a few substitutions. `vars` holds the
+/// user defined variables and the ones derived from the project, such as `license_section`.
+/// `members` are the workspace members a `{{#each members}}` loop repeats over. `binary` tells
+/// which installation section the layout uses, and `strings` are the fixed strings it adds.
+/// `registry` tells which `{{badge.<key>}}` placeholders name a badge.
+#[allow(clippy::too_many_arguments)]
 pub fn render(
     template: Option<String>,
     readme: String,
     cargo: &Manifest,
-    add_title: bool,
-    add_badges: bool,
-    add_license: bool,
+    vars: &BTreeMap<String, String>,
+    members: &[Member],
+    layout: &Layout,
+    registry: &BadgeRegistry,
+    strings: &Strings,
+    binary: bool,
+    strict: bool,
 ) -> Result<String, String> {
     let title: &str = &cargo.name;
 
-    let badges: Vec<&str> = cargo.badges.iter().map(AsRef::as_ref).collect();
-    let badges: &[&str] = badges.as_ref();
+    let laid_out = badge_layout::lay_out(&cargo.badges, layout.badge_layout);
+
+    // individual badges and badge groups, placed with `{{badge.<key>}}` and
+    // `{{badges.<category>}}`
+    let mut vars = vars.clone();
+    for (badge, markdown) in cargo.badges.iter().zip(&laid_out.badges) {
+        // a key given more than once, such as `github` with several workflows, places them all
+        vars.entry(format!("badge.{}", badge.key))
+            .and_modify(|badges| {
+                badges.push('\n');
+                badges.push_str(markdown);
+            })
+            .or_insert_with(|| markdown.clone());
+    }
+    for category in BadgeCategory::ALL {
+        let group: Vec<&str> = cargo
+            .badges
+            .iter()
+            .zip(&laid_out.badges)
+            .filter(|(badge, _)| badge.category == *category)
+            .map(|(_, markdown)| markdown.as_str())
+            .collect();
+        if !group.is_empty() {
+            let group = badge_layout::join(&group, layout.badge_layout);
+            vars.insert(format!("badges.{}", category.name()), group);
+        }
+    }
+    let vars = &vars;
+
+    let badges: Vec<&str> = laid_out.badges.iter().map(AsRef::as_ref).collect();
+    let badges = badge_layout::join(&badges, layout.badge_layout);
 
     let license: Option<&str> = cargo.license.as_ref().map(AsRef::as_ref);
 
     let version: &str = cargo.version.as_ref();
 
-    if let Some(template) = template {
-        process_template(template, readme, title, badges, license, version)
+    let license_section: Option<&str> = if layout.license_section {
+        vars.get("license_section").map(AsRef::as_ref)
     } else {
+        None
+    };
+
+    let readme = if let Some(template) = template {
+        let badge_keys: Vec<&str> = registry.providers().map(|p| p.info().key).collect();
+        process_template(
+            template,
+            readme,
+            title,
+            &badges,
+            license,
+            version,
+            vars,
+            members,
+            &badge_keys,
+            strict,
+        )?
+    } else {
+        let description = cargo.description.as_deref();
         process_string(
             readme,
             title,
-            badges,
+            version,
+            description,
+            &badges,
             license,
-            add_title,
-            add_badges,
-            add_license,
-        )
-    }
+            license_section,
+            binary,
+            layout,
+            strings,
+        )?
+    };
+
+    // the URLs of reference-style badges go at the bottom
+    Ok(badge_layout::append_definitions(
+        readme,
+        &laid_out.definitions,
+    ))
 }
 
 /// Process the substitutions of the template
@@ -41,22 +161,52 @@ pub fn render(
 /// - `{{readme}}` documentation extracted from the rust docs
 /// - `{{crate}}` crate name defined in `Cargo.toml`
 /// - `{{badges}}` badges defined in `Cargo.toml`
+/// - `{{badge.<key>}}` the badge defined under `<key>` in `Cargo.toml`
+/// - `{{badges.<category>}}` the badges of a category, such as `ci` or `coverage`
 /// - `{{license}}` license defined in `Cargo.toml`
 /// - `{{version}}` version defined in `Cargo.toml`
+/// - `{{license_section}}` license section generated from the license defined in `Cargo.toml`
+/// - `{{changelog}}` entry of `CHANGELOG.md` for the version defined in `Cargo.toml`
+/// - `{{summary}}` first paragraph of the documentation
+/// - `{{git.commit}}`, `{{git.short_commit}}`, `{{git.tag}}`, `{{git.branch}}`,
+///   `{{git.default_branch}}` and `{{git.remote_url}}` read from the local git repository
+/// - `{{#each members}}...{{/each}}` repeated for each of `members`, with their `{{name}}`,
+///   `{{version}}`, `{{description}}`, `{{summary}}`, `{{badges}}` and `{{path}}`
+///
+/// plus every user defined variable in `vars`. `badge_keys` are the keys a `{{badge.<key>}}` can
+/// name. A template with a `{{#each members}}` loop is an index of the workspace and does not
+/// need `{{readme}}`.
+///
+/// In strict mode, unknown placeholders and missing badges or license are errors instead of being
+/// left verbatim or only warned about.
+#[allow(clippy::too_many_arguments)]
 fn process_template(
     mut template: String,
     readme: String,
     title: &str,
-    badges: &[&str],
+    badges: &str,
     license: Option<&str>,
     version: &str,
+    vars: &BTreeMap<String, String>,
+    members: &[Member],
+    badge_keys: &[&str],
+    strict: bool,
 ) -> Result<String, String> {
     template = template.trim_end_matches("\n").to_owned();
 
-    if !template.contains("{{readme}}") {
+    if !template.contains("{{readme}}") && !members::uses_members(&template) {
         return Err("Missing `{{readme}}` in template".to_owned());
     }
 
+    if strict {
+        check_placeholders(&template, vars, badge_keys)?;
+    }
+
+    // the variables of the members take precedence inside the loops
+    template = members::expand_members(&template, members)
+        .trim_end_matches("\n")
+        .to_owned();
+
     if template.contains("{{crate}}") {
         template = template.replace("{{crate}}", title);
     }
@@ -64,61 +214,335 @@ fn process_template(
     if template.contains("{{badges}}") {
         if badges.is_empty() {
             let msg = "`{{badges}}` was found in template but no badges were provided";
-            eprintln!("Warn: {msg}");
+            warn_or_fail(msg, strict)?;
         }
-        let badges = badges.join("\n");
-        template = template.replace("{{badges}}", &badges);
+        template = template.replace("{{badges}}", badges);
     }
 
     if template.contains("{{license}}") {
         if license.is_none() {
             let msg = "`{{license}}` was found in template but no license was provided";
-            eprintln!("Warn: {msg}");
+            warn_or_fail(msg, strict)?;
         }
         template = template.replace("{{license}}", license.unwrap_or(""));
     }
 
     template = template.replace("{{version}}", version);
 
+    for (name, missing) in DERIVED_VARIABLES {
+        let placeholder = format!("{{{{{name}}}}}");
+        if template.contains(&placeholder) && !vars.contains_key(*name) {
+            let msg = format!("`{placeholder}` was found in template but {missing}");
+            warn_or_fail(&msg, strict)?;
+            template = template.replace(&placeholder, "");
+        }
+    }
+
+    template = replace_missing_badges(&template, vars, badge_keys, strict)?;
+
+    for (name, value) in vars {
+        template = template.replace(&format!("{{{{{name}}}}}"), value);
+    }
+
     let result = template.replace("{{readme}}", &readme);
     Ok(result)
 }
 
+/// Remove the `{{badge.<key>}}` and `{{badges.<category>}}` placeholders of badges that are not
+/// defined, with a warning
+///
+/// Placeholders naming an unknown badge or category are left alone.
+fn replace_missing_badges(
+    template: &str,
+    vars: &BTreeMap<String, String>,
+    badge_keys: &[&str],
+    strict: bool,
+) -> Result<String, String> {
+    let mut result = String::with_capacity(template.len());
+    let mut last = 0;
+
+    for cap in RE_BADGE_PLACEHOLDER.captures_iter(template) {
+        let token = cap.get(0).unwrap();
+        let name = &token.as_str()[2..token.len() - 2];
+        if vars.contains_key(name) || !is_badge_variable(name, badge_keys) {
+            continue;
+        }
+
+        let msg = if &cap[1] == "badge" {
+            format!(
+                "`{}` was found in template but no `{}` badge was provided",
+                token.as_str(),
+                &cap[2]
+            )
+        } else {
+            format!(
+                "`{}` was found in template but no {} badges were provided",
+                token.as_str(),
+                &cap[2]
+            )
+        };
+        warn_or_fail(&msg, strict)?;
+
+        result.push_str(&template[last..token.start()]);
+        last = token.end();
+    }
+
+    result.push_str(&template[last..]);
+    Ok(result)
+}
+
+/// Every `badge.<key>` and `badges.<category>` variable a template can use, with the keys of
+/// `badge_keys` and of the automatic badges
+fn badge_variables<'a>(badge_keys: &'a [&str]) -> impl Iterator<Item = String> + 'a {
+    let badges = badge_keys
+        .iter()
+        .copied()
+        .chain(AUTO_BADGES.iter().map(|b| b.key))
+        .map(|key| format!("badge.{key}"));
+    let groups = BadgeCategory::ALL
+        .iter()
+        .map(|c| format!("badges.{}", c.name()));
+    badges.chain(groups)
+}
+
+fn is_badge_variable(name: &str, badge_keys: &[&str]) -> bool {
+    badge_variables(badge_keys).any(|known| known == name)
+}
+
+/// Check that user defined variables have usable names that do not shadow a built-in variable
+pub fn check_user_variables(vars: &BTreeMap<String, String>) -> Result<(), String> {
+    for name in vars.keys() {
+        if TEMPLATE_VARIABLES.contains(&name.as_str()) {
+            return Err(format!(
+                "`{name}` is a built-in template variable and cannot be set"
+            ));
+        }
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid {
+            return Err(format!(
+                "Invalid template variable name `{name}`: only letters, digits, `_` and `-` \
+                 are allowed"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check that every `{{...}}` placeholder in the template is a known variable
+///
+/// All unknown placeholders are reported at once, each with its position and a suggestion when a
+/// known variable is spelled similarly.
+fn check_placeholders(
+    template: &str,
+    vars: &BTreeMap<String, String>,
+    badge_keys: &[&str],
+) -> Result<(), String> {
+    let badge_variables: Vec<String> = badge_variables(badge_keys).collect();
+    let known: Vec<&str> = TEMPLATE_VARIABLES
+        .iter()
+        .copied()
+        .chain(badge_variables.iter().map(String::as_str))
+        .chain(vars.keys().map(String::as_str))
+        .collect();
+
+    let loops = members::loops(template);
+
+    let errors: Vec<String> = RE_PLACEHOLDER
+        .captures_iter(template)
+        .filter_map(|cap| {
+            let token = cap.get(0).unwrap();
+            // substitution matches the name exactly, so `{{ crate }}` is not `{{crate}}`
+            let name = &cap[1];
+            if known.contains(&name) {
+                return None;
+            }
+
+            // the loop tags, and the member variables inside a loop
+            let in_loop = loops.iter().any(|l| l.contains(&token.start()));
+            if in_loop && (token.as_str().starts_with("{{#each") || name == "/each") {
+                return None;
+            }
+            if in_loop && MEMBER_VARIABLES.contains(&name) {
+                return None;
+            }
+
+            let (line, column) = line_column(template, token.start());
+            let mut msg = format!(
+                "Unknown template variable `{}` at line {}, column {}",
+                token.as_str(),
+                line,
+                column
+            );
+            let trimmed = name.trim();
+            let suggestion =
+                if known.contains(&trimmed) || (in_loop && MEMBER_VARIABLES.contains(&trimmed)) {
+                    Some(trimmed)
+                } else {
+                    suggest::did_you_mean(trimmed, known.iter().copied())
+                };
+            if let Some(known) = suggestion {
+                msg.push_str(&format!(" (did you mean `{{{{{known}}}}}`?)"));
+            }
+            Some(msg)
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("\n"))
+    }
+}
+
+/// 1-based line and column (in chars) of a byte offset
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+/// Print a warning, or turn it into an error in strict mode
+pub(super) fn warn_or_fail(msg: &str, strict: bool) -> Result<(), String> {
+    if strict {
+        return Err(msg.to_owned());
+    }
+    eprintln!("Warn: {msg}");
+    Ok(())
+}
+
 /// Process output without template
+///
+/// `title` is the crate name, shown as the title unless the layout gives another text. When
+/// given, `license_section` is appended instead of the license line.
+#[allow(clippy::too_many_arguments)]
 fn process_string(
     mut readme: String,
     title: &str,
-    badges: &[&str],
+    version: &str,
+    description: Option<&str>,
+    badges: &str,
     license: Option<&str>,
-    add_title: bool,
-    add_badges: bool,
-    add_license: bool,
+    license_section: Option<&str>,
+    binary: bool,
+    layout: &Layout,
+    strings: &Strings,
 ) -> Result<String, String> {
-    if add_title {
-        readme = prepend_title(readme, title);
+    let crate_name = title;
+    let title = layout.title.as_deref().unwrap_or(crate_name);
+
+    let description = if layout.subtitle {
+        if description.is_none() {
+            eprintln!("Warn: no description was provided for the subtitle");
+        }
+        description
+    } else {
+        None
+    };
+    let docs_link = layout.docs_link.then(|| docs_link(crate_name, strings));
+
+    if layout.centered_header {
+        let mut header: Vec<String> = Vec::new();
+        if layout.add_title {
+            header.push(format!("# {}", title));
+        }
+        header.extend(description.map(str::to_owned));
+        if layout.add_badges && !badges.is_empty() {
+            header.push(badges.to_owned());
+        }
+        header.extend(docs_link);
+        if !header.is_empty() {
+            let header = format!(
+                "<div align=\"center\">\n\n{}\n\n</div>",
+                header.join("\n\n")
+            );
+            readme = prepend_block(readme, &header);
+        }
+    } else {
+        if let Some(docs_link) = docs_link {
+            readme = prepend_block(readme, &docs_link);
+        }
+        if let Some(description) = description {
+            readme = prepend_block(readme, description);
+        }
+        if layout.add_title {
+            readme = prepend_title(readme, title);
+        }
+        if layout.add_badges {
+            readme = prepend_badges(readme, badges);
+        }
     }
 
-    if add_badges {
-        readme = prepend_badges(readme, badges);
+    if layout.install_section {
+        readme = append_block(
+            readme,
+            &install_section(crate_name, version, binary, strings),
+        );
     }
 
-    if add_license {
-        if let Some(license) = license {
-            readme = append_license(readme, license);
+    if layout.add_license {
+        if let Some(section) = license_section {
+            readme = append_license_section(readme, section, strings);
+        } else if let Some(license) = license {
+            readme = append_license(readme, license, strings);
         }
     }
 
     Ok(readme)
 }
 
+/// Link to the documentation of the crate on docs.rs
+fn docs_link(crate_name: &str, strings: &Strings) -> String {
+    format!(
+        "[{}](https://docs.rs/{})",
+        strings.documentation, crate_name
+    )
+}
+
+/// Installation section, `cargo install` for binaries and a `[dependencies]` line for libraries
+fn install_section(crate_name: &str, version: &str, binary: bool, strings: &Strings) -> String {
+    if binary {
+        format!(
+            "## {}\n\n```sh\ncargo install {}\n```",
+            strings.installation, crate_name
+        )
+    } else {
+        format!(
+            "## {}\n\n{}\n\n```toml\n[dependencies]\n{} = \"{}\"\n```",
+            strings.installation, strings.add_dependency, crate_name, version
+        )
+    }
+}
+
+/// Prepend a block to output string
+fn prepend_block(readme: String, block: &str) -> String {
+    if !readme.trim().is_empty() {
+        format!("{}\n\n{}", block, readme)
+    } else {
+        block.to_owned()
+    }
+}
+
+/// Append a block to output string
+fn append_block(readme: String, block: &str) -> String {
+    if !readme.trim().is_empty() {
+        format!("{}\n\n{}", readme, block)
+    } else {
+        block.to_owned()
+    }
+}
+
 /// Prepend badges to output string
-fn prepend_badges(readme: String, badges: &[&str]) -> String {
+fn prepend_badges(readme: String, badges: &str) -> String {
     if !badges.is_empty() {
-        let badges = badges.join("\n");
         if !readme.is_empty() {
             format!("{}\n\n{}", badges, readme)
         } else {
-            badges
+            badges.to_owned()
         }
     } else {
         readme
@@ -136,8 +560,8 @@ fn prepend_title(readme: String, crate_name: &str) -> String {
 }
 
 /// Append license to output string
-fn append_license(readme: String, license: &str) -> String {
-    let license = format!("License: {}", license);
+fn append_license(readme: String, license: &str, strings: &Strings) -> String {
+    let license = format!("{}{}", strings.license, license);
     if !readme.trim().is_empty() {
         format!("{}\n\n{}", readme, license)
     } else {
@@ -145,8 +569,40 @@ fn append_license(readme: String, license: &str) -> String {
     }
 }
 
+/// Append a license section to output string
+fn append_license_section(readme: String, section: &str, strings: &Strings) -> String {
+    let section = format!("## {}\n\n{}", strings.license_heading, section);
+    if !readme.trim().is_empty() {
+        format!("{}\n\n{}", readme, section)
+    } else {
+        section
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
+    use super::Layout;
+    use crate::config::BadgeRegistry;
+    use crate::readme::i18n::ENGLISH;
+
+    fn badge_keys() -> Vec<&'static str> {
+        BadgeRegistry::new()
+            .providers()
+            .map(|p| p.info().key)
+            .collect()
+    }
+
+    fn layout(add_title: bool, add_badges: bool, add_license: bool) -> Layout {
+        Layout {
+            add_title,
+            add_badges,
+            add_license,
+            ..Layout::default()
+        }
+    }
+
     const TEMPLATE_MINIMAL: &str = "{{readme}}";
     const TEMPLATE_WITH_TITLE: &str = "# {{crate}}\n\n{{readme}}";
     const TEMPLATE_WITH_BADGES: &str = "{{badges}}\n\n{{readme}}";
@@ -158,7 +614,18 @@ mod tests {
     // process template
     #[test]
     fn template_without_readme_should_fail() {
-        let result = super::process_template(String::new(), String::new(), "", &[], None, "");
+        let result = super::process_template(
+            String::new(),
+            String::new(),
+            "",
+            "",
+            None,
+            "",
+            &BTreeMap::new(),
+            &[],
+            &badge_keys(),
+            false,
+        );
         assert!(result.is_err());
         assert_eq!("Missing `{{readme}}` in template", result.unwrap_err());
     }
@@ -169,9 +636,13 @@ mod tests {
             TEMPLATE_WITH_BADGES.to_owned(),
             "readme".to_owned(),
             "",
-            &[],
+            "",
             None,
             "",
+            &BTreeMap::new(),
+            &[],
+            &badge_keys(),
+            false,
         );
         assert!(result.is_ok());
         assert_eq!("\n\nreadme", result.unwrap());
@@ -183,23 +654,92 @@ mod tests {
             TEMPLATE_WITH_LICENSE.to_owned(),
             "readme".to_owned(),
             "",
-            &[],
+            "",
             None,
             "",
+            &BTreeMap::new(),
+            &[],
+            &badge_keys(),
+            false,
         );
         assert!(result.is_ok());
         assert_eq!("readme\n\n", result.unwrap());
     }
 
+    #[test]
+    fn template_with_missing_badge_placeholders_should_warn() {
+        let mut vars = BTreeMap::new();
+        vars.insert("badge.github".to_owned(), "github".to_owned());
+        let result = super::process_template(
+            "{{badge.github}} {{badge.codecov}}|{{badges.ci}}|{{badge.nope}}\n{{readme}}"
+                .to_owned(),
+            "readme".to_owned(),
+            "",
+            "",
+            None,
+            "",
+            &vars,
+            &[],
+            &badge_keys(),
+            false,
+        );
+        assert_eq!("github ||{{badge.nope}}\nreadme", result.unwrap());
+    }
+
+    #[test]
+    fn strict_template_with_missing_badge_group_should_fail() {
+        let result = super::process_template(
+            "{{badges.coverage}}\n{{readme}}".to_owned(),
+            "readme".to_owned(),
+            "",
+            "",
+            None,
+            "",
+            &BTreeMap::new(),
+            &[],
+            &badge_keys(),
+            true,
+        );
+        assert_eq!(
+            "`{{badges.coverage}}` was found in template but no coverage badges were provided",
+            result.unwrap_err()
+        );
+    }
+
+    #[test]
+    fn strict_template_with_unknown_badge() {
+        let result = super::process_template(
+            "{{badge.githb}}\n{{readme}}".to_owned(),
+            "readme".to_owned(),
+            "",
+            "",
+            None,
+            "",
+            &BTreeMap::new(),
+            &[],
+            &badge_keys(),
+            true,
+        );
+        assert_eq!(
+            "Unknown template variable `{{badge.githb}}` at line 1, column 1 (did you mean \
+             `{{badge.github}}`?)",
+            result.unwrap_err()
+        );
+    }
+
     #[test]
     fn template_minimal() {
         let result = super::process_template(
             TEMPLATE_MINIMAL.to_owned(),
             "readme".to_owned(),
             "",
-            &[],
+            "",
             None,
             "",
+            &BTreeMap::new(),
+            &[],
+            &badge_keys(),
+            false,
         );
         assert!(result.is_ok());
         assert_eq!("readme", result.unwrap());
@@ -211,9 +751,13 @@ mod tests {
             TEMPLATE_WITH_TITLE.to_owned(),
             "readme".to_owned(),
             "title",
-            &[],
+            "",
             None,
             "",
+            &BTreeMap::new(),
+            &[],
+            &badge_keys(),
+            false,
         );
         assert!(result.is_ok());
         assert_eq!("# title\n\nreadme", result.unwrap());
@@ -225,9 +769,13 @@ mod tests {
             TEMPLATE_WITH_BADGES.to_owned(),
             "readme".to_owned(),
             "",
-            &["badge1", "badge2"],
+            "badge1\nbadge2",
             None,
             "",
+            &BTreeMap::new(),
+            &[],
+            &badge_keys(),
+            false,
         );
         assert!(result.is_ok());
         assert_eq!("badge1\nbadge2\n\nreadme", result.unwrap());
@@ -239,9 +787,13 @@ mod tests {
             TEMPLATE_WITH_LICENSE.to_owned(),
             "readme".to_owned(),
             "",
-            &[],
+            "",
             Some("license"),
             "",
+            &BTreeMap::new(),
+            &[],
+            &badge_keys(),
+            false,
         );
         assert!(result.is_ok());
         assert_eq!("readme\n\nlicense", result.unwrap());
@@ -253,9 +805,13 @@ mod tests {
             TEMPLATE_WITH_VERSION.to_owned(),
             "readme".to_owned(),
             "",
-            &[],
+            "",
             None,
             "3.0.1",
+            &BTreeMap::new(),
+            &[],
+            &badge_keys(),
+            false,
         );
         assert!(result.is_ok());
         assert_eq!("readme\n\n3.0.1", result.unwrap());
@@ -267,9 +823,13 @@ mod tests {
             TEMPLATE_FULL.to_owned(),
             "readme".to_owned(),
             "title",
-            &["badge1", "badge2"],
+            "badge1\nbadge2",
             Some("license"),
             "3.0.2",
+            &BTreeMap::new(),
+            &[],
+            &badge_keys(),
+            false,
         );
         assert!(result.is_ok());
         assert_eq!(
@@ -278,18 +838,202 @@ mod tests {
         );
     }
 
+    #[test]
+    fn strict_template_with_unknown_variable_should_fail() {
+        let result = super::process_template(
+            "{{readme}}\n\nLicense: {{licence}}".to_owned(),
+            "readme".to_owned(),
+            "",
+            "",
+            Some("license"),
+            "",
+            &BTreeMap::new(),
+            &[],
+            &badge_keys(),
+            true,
+        );
+        assert_eq!(
+            "Unknown template variable `{{licence}}` at line 3, column 10 (did you mean `{{license}}`?)",
+            result.unwrap_err()
+        );
+    }
+
+    #[test]
+    fn strict_template_with_spaced_variable_should_fail() {
+        let result = super::process_template(
+            "# {{ crate }}\n\n{{readme}}".to_owned(),
+            "readme".to_owned(),
+            "title",
+            "",
+            None,
+            "",
+            &BTreeMap::new(),
+            &[],
+            &badge_keys(),
+            true,
+        );
+        assert_eq!(
+            "Unknown template variable `{{ crate }}` at line 1, column 3 (did you mean `{{crate}}`?)",
+            result.unwrap_err()
+        );
+    }
+
+    #[test]
+    fn strict_template_with_missing_badges_should_fail() {
+        let result = super::process_template(
+            TEMPLATE_WITH_BADGES.to_owned(),
+            "readme".to_owned(),
+            "",
+            "",
+            None,
+            "",
+            &BTreeMap::new(),
+            &[],
+            &badge_keys(),
+            true,
+        );
+        assert_eq!(
+            "`{{badges}}` was found in template but no badges were provided",
+            result.unwrap_err()
+        );
+    }
+
+    #[test]
+    fn strict_template_full() {
+        let result = super::process_template(
+            TEMPLATE_FULL.to_owned(),
+            "readme".to_owned(),
+            "title",
+            "badge1",
+            Some("license"),
+            "3.0.2",
+            &BTreeMap::new(),
+            &[],
+            &badge_keys(),
+            true,
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn non_strict_template_keeps_unknown_variable() {
+        let result = super::process_template(
+            "{{readme}} {{unknown}}".to_owned(),
+            "readme".to_owned(),
+            "",
+            "",
+            None,
+            "",
+            &BTreeMap::new(),
+            &[],
+            &badge_keys(),
+            false,
+        );
+        assert_eq!("readme {{unknown}}", result.unwrap());
+    }
+
+    #[test]
+    fn template_with_license_section_but_missing_license_should_warn() {
+        let result = super::process_template(
+            "{{readme}}\n\n{{license_section}}".to_owned(),
+            "readme".to_owned(),
+            "",
+            "",
+            None,
+            "",
+            &BTreeMap::new(),
+            &[],
+            &badge_keys(),
+            false,
+        );
+        assert_eq!("readme\n\n", result.unwrap());
+    }
+
+    #[test]
+    fn template_with_user_variables() {
+        let mut vars = BTreeMap::new();
+        vars.insert("msrv_note".to_owned(), "Requires Rust 1.80".to_owned());
+        let result = super::process_template(
+            "{{readme}}\n\n{{msrv_note}}".to_owned(),
+            "readme".to_owned(),
+            "",
+            "",
+            None,
+            "",
+            &vars,
+            &[],
+            &badge_keys(),
+            true,
+        );
+        assert_eq!("readme\n\nRequires Rust 1.80", result.unwrap());
+    }
+
+    #[test]
+    fn strict_template_suggests_user_variable() {
+        let mut vars = BTreeMap::new();
+        vars.insert("support_channel".to_owned(), "#help".to_owned());
+        let result = super::process_template(
+            "{{readme}} {{suport_channel}}".to_owned(),
+            "readme".to_owned(),
+            "",
+            "",
+            None,
+            "",
+            &vars,
+            &[],
+            &badge_keys(),
+            true,
+        );
+        assert_eq!(
+            "Unknown template variable `{{suport_channel}}` at line 1, column 12 \
+             (did you mean `{{support_channel}}`?)",
+            result.unwrap_err()
+        );
+    }
+
+    #[test]
+    fn user_variable_cannot_shadow_builtin() {
+        let mut vars = BTreeMap::new();
+        vars.insert("crate".to_owned(), "other".to_owned());
+        assert_eq!(
+            "`crate` is a built-in template variable and cannot be set",
+            super::check_user_variables(&vars).unwrap_err()
+        );
+    }
+
     // process string
     #[test]
     fn render_minimal() {
-        let result = super::process_string("readme".to_owned(), "", &[], None, false, false, false);
+        let result = super::process_string(
+            "readme".to_owned(),
+            "",
+            "",
+            None,
+            "",
+            None,
+            None,
+            false,
+            &layout(false, false, false),
+            &ENGLISH,
+        );
         assert!(result.is_ok());
         assert_eq!("readme", result.unwrap());
     }
 
     #[test]
     fn render_title() {
-        let result =
-            super::process_string("readme".to_owned(), "title", &[], None, true, false, false);
+        let result = super::process_string(
+            "readme".to_owned(),
+            "title",
+            "",
+            None,
+            "",
+            None,
+            None,
+            false,
+            &layout(true, false, false),
+            &ENGLISH,
+        );
         assert!(result.is_ok());
         assert_eq!("# title\n\nreadme", result.unwrap());
     }
@@ -299,11 +1043,14 @@ mod tests {
         let result = super::process_string(
             "readme".to_owned(),
             "",
-            &["badge1", "badge2"],
+            "",
+            None,
+            "badge1\nbadge2",
+            None,
             None,
             false,
-            true,
-            false,
+            &layout(false, true, false),
+            &ENGLISH,
         );
         assert!(result.is_ok());
         assert_eq!("badge1\nbadge2\n\nreadme", result.unwrap());
@@ -314,11 +1061,14 @@ mod tests {
         let result = super::process_string(
             "readme".to_owned(),
             "",
-            &[],
+            "",
+            None,
+            "",
             Some("license"),
+            None,
             false,
-            false,
-            true,
+            &layout(false, false, true),
+            &ENGLISH,
         );
         assert!(result.is_ok());
         assert_eq!("readme\n\nLicense: license", result.unwrap());
@@ -329,11 +1079,14 @@ mod tests {
         let result = super::process_string(
             "readme".to_owned(),
             "title",
-            &["badge1", "badge2"],
+            "",
+            None,
+            "badge1\nbadge2",
             Some("license"),
-            true,
-            true,
-            true,
+            None,
+            false,
+            &layout(true, true, true),
+            &ENGLISH,
         );
         assert!(result.is_ok());
         assert_eq!(
@@ -347,38 +1100,119 @@ mod tests {
         let result = super::process_string(
             "readme".to_owned(),
             "title",
-            &["badge1", "badge2"],
+            "",
+            None,
+            "badge1\nbadge2",
             Some("license"),
+            None,
             false,
-            false,
-            false,
+            &layout(false, false, false),
+            &ENGLISH,
         );
         assert!(result.is_ok());
         assert_eq!("readme", result.unwrap());
     }
 
+    #[test]
+    fn render_license_section() {
+        let result = super::process_string(
+            "readme".to_owned(),
+            "",
+            "",
+            None,
+            "",
+            Some("license"),
+            Some("Licensed under the license."),
+            false,
+            &layout(false, false, true),
+            &ENGLISH,
+        );
+        assert_eq!(
+            "readme\n\n## License\n\nLicensed under the license.",
+            result.unwrap()
+        );
+    }
+
+    #[test]
+    fn render_subtitle_docs_link_and_install_section() {
+        let layout = Layout {
+            add_title: true,
+            title: Some("Title".to_owned()),
+            subtitle: true,
+            docs_link: true,
+            install_section: true,
+            ..Layout::default()
+        };
+        let result = super::process_string(
+            "readme".to_owned(),
+            "my-crate",
+            "1.2.3",
+            Some("A crate."),
+            "",
+            None,
+            None,
+            false,
+            &layout,
+            &ENGLISH,
+        );
+        assert_eq!(
+            "# Title\n\nA crate.\n\n[Documentation](https://docs.rs/my-crate)\n\nreadme\n\n\
+             ## Installation\n\nAdd this to your `Cargo.toml`:\n\n\
+             ```toml\n[dependencies]\nmy-crate = \"1.2.3\"\n```",
+            result.unwrap()
+        );
+    }
+
+    #[test]
+    fn render_centered_header() {
+        let layout = Layout {
+            centered_header: true,
+            subtitle: true,
+            install_section: true,
+            ..layout(true, true, true)
+        };
+        let result = super::process_string(
+            "readme".to_owned(),
+            "title",
+            "1.2.3",
+            Some("A tool."),
+            "badge1\nbadge2",
+            Some("license"),
+            None,
+            true,
+            &layout,
+            &ENGLISH,
+        );
+        assert_eq!(
+            "<div align=\"center\">\n\n# title\n\nA tool.\n\nbadge1\nbadge2\n\n</div>\n\n\
+             readme\n\n## Installation\n\n```sh\ncargo install title\n```\n\n\
+             License: license",
+            result.unwrap()
+        );
+    }
+
     // prepend badges
     #[test]
     fn prepend_badges_with_filled_readme_and_non_empty_badges() {
-        let result = super::prepend_badges("readme".into(), &["badge1", "badge2"]);
+        let result = super::prepend_badges("readme".into(), "badge1\nbadge2");
         assert_eq!("badge1\nbadge2\n\nreadme", result);
     }
 
     #[test]
     fn prepend_badges_with_empty_readme_and_non_empty_badges() {
-        let result = super::prepend_badges("".into(), &["badge1", "badge2"]);
+        let result = super::prepend_badges("".into(), "badge1\nbadge2");
         assert_eq!("badge1\nbadge2", result);
     }
 
     #[test]
     fn prepend_badges_with_filled_readme_and_empty_badges() {
-        let result = super::prepend_badges("readme".into(), &[]);
+        let result = super::prepend_badges("readme".into(), "");
         assert_eq!("readme", result);
     }
 
     #[test]
     fn prepend_badges_with_empty_readme_and_empty_badges() {
-        let result = super::prepend_badges("".into(), &[]);
+        let result = super::prepend_badges("".into(), "");
         assert_eq!("", result);
     }
 
@@ -398,13 +1232,13 @@ mod tests {
     // append license
     #[test]
     fn append_license_with_filled_readme() {
-        let result = super::append_license("readme".into(), "license");
+        let result = super::append_license("readme".into(), "license", &ENGLISH);
         assert_eq!("readme\n\nLicense: license", result);
     }
 
     #[test]
     fn append_license_with_empty_readme() {
-        let result = super::append_license("".into(), "license");
+        let result = super::append_license("".into(), "license", &ENGLISH);
         assert_eq!("License: license", result);
     }
 }
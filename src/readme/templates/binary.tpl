@@ -0,0 +1,13 @@
+{{badges}}
+
+# {{crate}}
+
+{{readme}}
+
+## Installation
+
+```sh
+cargo install {{crate}}
+```
+
+License: {{license}}
@@ -0,0 +1,7 @@
+{{badges}}
+
+# {{crate}}
+
+{{readme}}
+
+License: {{license}}
@@ -0,0 +1,16 @@
+{{badges}}
+
+# {{crate}}
+
+{{readme}}
+
+## Usage
+
+Add this to your `Cargo.toml`:
+
+```toml
+[dependencies]
+{{crate}} = "{{version}}"
+```
+
+License: {{license}}
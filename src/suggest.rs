@@ -0,0 +1,56 @@
+//! "Did you mean" suggestions for misspelled names
+
+/// Find the candidate closest to `name`, if any is close enough to be a plausible typo.
+pub fn did_you_mean<'a, I>(name: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    // allow roughly one edit for every three characters, but always at least one
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (levenshtein(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Edit distance between two strings, counted in chars
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levenshtein_distance() {
+        assert_eq!(0, levenshtein("license", "license"));
+        assert_eq!(1, levenshtein("licence", "license"));
+        assert_eq!(3, levenshtein("", "abc"));
+        assert_eq!(2, levenshtein("travis_ci", "travis-cx"));
+    }
+
+    #[test]
+    fn suggest_closest_candidate() {
+        let candidates = ["readme", "crate", "badges", "license", "version"];
+        assert_eq!(Some("license"), did_you_mean("licence", candidates));
+        assert_eq!(Some("badges"), did_you_mean("badge", candidates));
+        assert_eq!(None, did_you_mean("support_channel", candidates));
+    }
+}
@@ -0,0 +1,28 @@
+use assert_cmd::Command;
+
+const EXPECTED: &str = r#"[![Crates.io](https://img.shields.io/crates/v/auto-badges.svg)](https://crates.io/crates/auto-badges)
+[![Documentation](https://docs.rs/auto-badges/badge.svg)](https://docs.rs/auto-badges)
+[![Downloads](https://img.shields.io/crates/d/auto-badges.svg)](https://crates.io/crates/auto-badges)
+![License: MIT OR Apache-2.0](https://img.shields.io/badge/license-MIT%20OR%20Apache--2.0-blue.svg)
+![MSRV: 1.80](https://img.shields.io/badge/rustc-1.80%2B-blue.svg)
+![Maintenance](https://img.shields.io/badge/maintenance-actively--developed-brightgreen.svg)
+[![Dependency Status](https://deps.rs/crate/auto-badges/1.2.0/status.svg)](https://deps.rs/crate/auto-badges/1.2.0)
+
+# auto-badges
+
+Test crate for cargo-readme
+
+License: MIT OR Apache-2.0
+"#;
+
+#[test]
+fn auto_badges_grouped_with_builtin_ones() {
+    let args = ["readme", "--project-root", "tests/auto-badges"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED);
+}
@@ -0,0 +1,65 @@
+use assert_cmd::Command;
+
+const EXPECTED_REFERENCE: &str = r#"[![Crates.io][badge-crates-io]][badge-crates-io-link]
+[![Workflow Status][badge-github]][badge-github-link]
+[![Percentage of issues still open][badge-is-it-maintained-open-issues]][badge-is-it-maintained-open-issues-link]
+![Maintenance][badge-maintenance]
+
+# badge-layout
+
+Test crate for cargo-readme
+
+License: MIT
+
+[badge-crates-io]: https://img.shields.io/crates/v/badge-layout.svg
+[badge-crates-io-link]: https://crates.io/crates/badge-layout
+[badge-github]: https://github.com/owner/repo/actions/workflows/ci.yml/badge.svg
+[badge-github-link]: https://github.com/owner/repo/actions/workflows/ci.yml
+[badge-is-it-maintained-open-issues]: https://isitmaintained.com/badge/open/owner/repo.svg
+[badge-is-it-maintained-open-issues-link]: https://isitmaintained.com/project/owner/repo "Percentage of issues still open"
+[badge-maintenance]: https://img.shields.io/badge/maintenance-experimental-blue.svg?style=for-the-badge
+"#;
+
+const EXPECTED_HTML: &str = r#"<p align="center">
+<a href="https://crates.io/crates/badge-layout"><img src="https://img.shields.io/crates/v/badge-layout.svg" alt="Crates.io" height="20"></a>
+<a href="https://github.com/owner/repo/actions/workflows/ci.yml"><img src="https://github.com/owner/repo/actions/workflows/ci.yml/badge.svg" alt="Workflow Status" height="20"></a>
+<a href="https://isitmaintained.com/project/owner/repo" title="Percentage of issues still open"><img src="https://isitmaintained.com/badge/open/owner/repo.svg" alt="Percentage of issues still open" height="20"></a>
+<img src="https://img.shields.io/badge/maintenance-experimental-blue.svg?style=for-the-badge" alt="Maintenance" height="28">
+</p>
+
+# badge-layout
+
+Test crate for cargo-readme
+
+License: MIT
+"#;
+
+#[test]
+fn reference_layout_from_manifest() {
+    let args = ["readme", "--project-root", "tests/badge-layout"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED_REFERENCE);
+}
+
+#[test]
+fn html_layout_from_command_line() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/badge-layout",
+        "--badge-layout",
+        "html",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED_HTML);
+}
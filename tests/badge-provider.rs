@@ -0,0 +1,75 @@
+use std::fs::File;
+use std::path::Path;
+
+use cargo_readme::{BadgeAttrs, BadgeCategory, BadgeContext, BadgeInfo, BadgeProvider};
+use cargo_readme::{BadgeRegistry, GenerateOptions};
+
+struct Chat(&'static BadgeInfo);
+
+const CHAT: BadgeInfo = BadgeInfo {
+    key: "chat",
+    official: false,
+    category: BadgeCategory::Custom,
+    required: &["repository"],
+    optional: &["service"],
+    values: &[],
+};
+
+const FORUM: BadgeInfo = BadgeInfo {
+    key: "forum",
+    ..CHAT
+};
+
+impl BadgeProvider for Chat {
+    fn info(&self) -> &BadgeInfo {
+        self.0
+    }
+
+    fn render(&self, attrs: BadgeAttrs, _: &BadgeContext) -> Result<String, String> {
+        Ok(format!(
+            "![{}](https://chat.example/{}/{}.svg)",
+            self.0.key, attrs["service"], attrs["repository"]
+        ))
+    }
+}
+
+fn generate(template: &str) -> Result<String, String> {
+    let project_root = Path::new("tests/badge-provider");
+    let mut source = File::open(project_root.join("src/lib.rs")).unwrap();
+    let mut template = File::open(project_root.join(template)).unwrap();
+
+    let mut badges = BadgeRegistry::new();
+    badges.register(Chat(&CHAT));
+    badges.register(Chat(&FORUM));
+
+    let options = GenerateOptions {
+        indent_headings: false,
+        strict: true,
+        ..GenerateOptions::default()
+    };
+    let cargo = cargo_readme::read_manifest(project_root)?;
+    cargo_readme::generate_readme_with(
+        project_root,
+        cargo,
+        &mut source,
+        Some(&mut template),
+        &badges,
+        &options,
+    )
+}
+
+#[test]
+fn registered_badge_placeholder_in_strict_template() {
+    assert_eq!(
+        "![chat](https://chat.example/github/owner/repo.svg)\n\nTest crate for cargo-readme",
+        generate("README.tpl").unwrap()
+    );
+}
+
+#[test]
+fn registered_badge_placeholder_without_entry_should_fail_in_strict_mode() {
+    assert_eq!(
+        "`{{badge.forum}}` was found in template but no `forum` badge was provided",
+        generate("MISSING.tpl").unwrap_err()
+    );
+}
@@ -0,0 +1,3 @@
+{{badge.chat}}
+
+{{readme}}
@@ -0,0 +1,26 @@
+use assert_cmd::Command;
+
+const EXPECTED: &str = r#"[![Crates.io](https://img.shields.io/crates/v/badge-style.svg?style=flat-square&color=orange)](https://crates.io/crates/badge-style)
+![License: MIT](https://img.shields.io/badge/license-MIT-blue.svg?style=flat-square&color=orange)
+[![Build Status](https://travis-ci.org/owner/repo.svg?branch=master)](https://travis-ci.org/owner/repo)
+![Maintenance](https://img.shields.io/badge/maintenance-experimental-blue.svg?style=for-the-badge&logo=rust&color=orange)
+[![chat](https://img.shields.io/badge/chat-on%20matrix-blue?style=flat-square&label=matrix&color=orange)](https://matrix.to/#/#badge-style:matrix.org)
+
+# badge-style
+
+Test crate for cargo-readme
+
+License: MIT
+"#;
+
+#[test]
+fn badge_style_applies_to_shields_badges() {
+    let args = ["readme", "--project-root", "tests/badge-style"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED);
+}
@@ -0,0 +1,22 @@
+use assert_cmd::Command;
+
+const EXPECTED: &str = r#"[![Build Status](https://gitlab.com/group/subgroup/project/badges/master/pipeline.svg)](https://gitlab.com/group/subgroup/project/commits/master)
+[![Coverage Status](https://codecov.io/gl/group/subgroup/project/branch/develop/graph/badge.svg)](https://codecov.io/gl/group/subgroup/project)
+[![Coverage Status](https://coveralls.io/repos/github/someone/else/badge.svg?branch=master)](https://coveralls.io/github/someone/else?branch=master)
+
+# badges-inferred
+
+Test crate for cargo-readme
+"#;
+
+#[test]
+fn badge_repository_from_package_repository() {
+    let args = ["readme", "--project-root", "tests/badges-inferred"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED);
+}
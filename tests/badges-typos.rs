@@ -0,0 +1,34 @@
+use assert_cmd::Command;
+
+const EXPECTED_WARNINGS: &str = "Warn: badge `codecov` has `style`, but only badges served by shields.io can be styled
+Warn: badge `github` has unknown attribute `workflow-fle` (did you mean `workflow-file`?)
+Warn: badge `maintenance` has invalid `status` `activly-developed`, expected one of: actively-developed, passively-maintained, as-is, experimental, looking-for-maintainer, deprecated, none (did you mean `actively-developed`?)
+Warn: unknown badge `travis_ci` in [badges] (did you mean `travis-ci`?)
+";
+
+const EXPECTED_ERROR: &str =
+    "Error: badge `codecov` has `style`, but only badges served by shields.io can be styled\n";
+
+#[test]
+fn badge_typos_warn() {
+    let args = ["readme", "--project-root", "tests/badges-typos"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stderr(EXPECTED_WARNINGS);
+}
+
+#[test]
+fn badge_typos_fail_in_strict_mode() {
+    let args = ["readme", "--project-root", "tests/badges-typos", "--strict"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .failure()
+        .stderr(EXPECTED_ERROR);
+}
@@ -6,6 +6,9 @@ const EXPECTED: &str = r#"[![Crates.io](https://img.shields.io/crates/v/readme-t
 [![Build Status](https://gitlab.com/cargo-readme/test/badges/master/pipeline.svg)](https://gitlab.com/cargo-readme/test/commits/master)
 [![Build Status](https://travis-ci.org/cargo-readme/test.svg?branch=master)](https://travis-ci.org/cargo-readme/test)
 [![Workflow Status](https://github.com/cargo-readme/test/workflows/main/badge.svg)](https://github.com/cargo-readme/test/actions?query=workflow%3A%22main%22)
+[![Build Status](https://dev.azure.com/cargo-readme/test/_apis/build/status/cargo-readme.test)](https://dev.azure.com/cargo-readme/test/_build/latest?definitionId=2)
+[![Build Status](https://img.shields.io/bitbucket/pipelines/cargo-readme/test/main.svg)](https://bitbucket.org/cargo-readme/test/addon/pipelines/home#!/results/branch/main/page/1)
+[![Build Status](https://api.cirrus-ci.com/github/cargo-readme/test.svg?branch=master)](https://cirrus-ci.com/github/cargo-readme/test)
 [![Coverage Status](https://codecov.io/gh/cargo-readme/test/branch/master/graph/badge.svg)](https://codecov.io/gh/cargo-readme/test)
 [![Coverage Status](https://coveralls.io/repos/github/cargo-readme/test/badge.svg?branch=main)](https://coveralls.io/github/cargo-readme/test?branch=main)
 [![Average time to resolve an issue](https://isitmaintained.com/badge/resolution/cargo-readme/test.svg)](https://isitmaintained.com/project/cargo-readme/test "Average time to resolve an issue")
@@ -31,37 +34,133 @@ fn badges() {
         .stdout(EXPECTED);
 }
 
+const EXPECTED_ROWS: &str = r#"# readme-test
+
+[![Crates.io](https://img.shields.io/crates/v/readme-test.svg)](https://crates.io/crates/readme-test) ![Maintenance](https://img.shields.io/badge/maintenance-actively--developed-brightgreen.svg)
+
+[![Build Status](https://ci.appveyor.com/api/projects/status/github/cargo-readme/test?branch=master&svg=true)](https://ci.appveyor.com/project/cargo-readme/test/branch/master)
+[![Build Status](https://circleci.com/gh/cargo-readme/test/tree/master.svg?style=shield)](https://circleci.com/gh/cargo-readme/test/tree/master)
+[![Build Status](https://gitlab.com/cargo-readme/test/badges/master/pipeline.svg)](https://gitlab.com/cargo-readme/test/commits/master)
+[![Build Status](https://travis-ci.org/cargo-readme/test.svg?branch=master)](https://travis-ci.org/cargo-readme/test)
+[![Workflow Status](https://github.com/cargo-readme/test/workflows/main/badge.svg)](https://github.com/cargo-readme/test/actions?query=workflow%3A%22main%22)
+[![Build Status](https://dev.azure.com/cargo-readme/test/_apis/build/status/cargo-readme.test)](https://dev.azure.com/cargo-readme/test/_build/latest?definitionId=2)
+[![Build Status](https://img.shields.io/bitbucket/pipelines/cargo-readme/test/main.svg)](https://bitbucket.org/cargo-readme/test/addon/pipelines/home#!/results/branch/main/page/1)
+[![Build Status](https://api.cirrus-ci.com/github/cargo-readme/test.svg?branch=master)](https://cirrus-ci.com/github/cargo-readme/test)
+
+[![Coverage Status](https://codecov.io/gh/cargo-readme/test/branch/master/graph/badge.svg)](https://codecov.io/gh/cargo-readme/test)
+[![Coverage Status](https://coveralls.io/repos/github/cargo-readme/test/badge.svg?branch=main)](https://coveralls.io/github/cargo-readme/test?branch=main)
+
+Test crate for cargo-readme
+"#;
+
+#[test]
+fn badge_placeholders() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/badges",
+        "--template",
+        "ROWS.tpl",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED_ROWS);
+}
+
 const EXPECTED_LIST: &str = r#"Supported badges (add under [badges] in Cargo.toml):
 
   crates-io  (cargo-readme extension)
-    optional: crate
+    category: package
+    optional: crate, style, logo, label, color
   appveyor
+    category: ci
     required: repository
     optional: branch, service
+    service: one of github, bitbucket, gitlab
   circle-ci
+    category: ci
     required: repository
     optional: branch, service
+    service: one of github, bitbucket, gitlab
   gitlab
+    category: ci
     required: repository
-    optional: branch
+    optional: branch, host, instance
   travis-ci
+    category: ci
     required: repository
     optional: branch
   github  (cargo-readme extension)
+    category: ci
+    required: repository
+    optional: workflow-file, branch, event, workflow, host
+  azure-devops
+    category: ci
+    required: project, pipeline
+    optional: build
+  bitbucket-pipelines
+    category: ci
+    required: repository, branch
+    optional: style, logo, label, color
+  cirrus-ci
+    category: ci
     required: repository
-    optional: workflow
+    optional: branch
+  gitea  (cargo-readme extension)
+    category: ci
+    required: repository, workflow-file, host
+    optional: branch, event
+  forgejo  (cargo-readme extension)
+    category: ci
+    required: repository, workflow-file
+    optional: branch, event, host
+  woodpecker  (cargo-readme extension)
+    category: ci
+    required: repository, host
+    optional: branch
   codecov
+    category: coverage
     required: repository
-    optional: branch, service
+    optional: branch, service, host
+    service: one of github, bitbucket, gitlab, github-enterprise, bitbucket-server, gitlab-enterprise
   coveralls
+    category: coverage
     required: repository
     optional: branch, service
+    service: one of github, bitbucket, gitlab
   is-it-maintained-issue-resolution
+    category: maintenance
     required: repository
   is-it-maintained-open-issues
+    category: maintenance
     required: repository
   maintenance
+    category: maintenance
     required: status
+    optional: style, logo, label, color
+    status: one of actively-developed, passively-maintained, as-is, experimental, looking-for-maintainer, deprecated, none
+
+Badges derived from the package (list in `auto-badges` under [package.metadata.readme]):
+
+  docs-rs
+    category: package
+    from: package.name
+  downloads
+    category: package
+    from: package.name
+  license
+    category: package
+    from: package.license
+  msrv
+    category: package
+    from: package.rust-version
+  deps-rs
+    category: maintenance
+    from: package.version
 "#;
 
 #[test]
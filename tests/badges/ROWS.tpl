@@ -0,0 +1,9 @@
+# {{crate}}
+
+{{badge.crates-io}} {{badge.maintenance}}
+
+{{badges.ci}}
+
+{{badges.coverage}}
+
+{{readme}}
@@ -0,0 +1,94 @@
+use assert_cmd::Command;
+
+const EXPECTED: &str = r#"[![Build Status](https://travis-ci.org/livioribeiro/cargo-readme.svg?branch=master)](https://travis-ci.org/livioribeiro/cargo-readme)
+
+# readme-test
+
+Test crate for cargo-readme
+
+## Installation
+
+```sh
+cargo install readme-test
+```
+
+License: MIT
+"#;
+
+const EXPECTED_PRINT: &str = "# {{crate}}\n\n{{readme}}\n";
+
+const EXPECTED_UNKNOWN: &str = "Error: Unknown builtin template `miniml` \
+     (did you mean `builtin:minimal`?), available templates: default, minimal, dual-license, \
+     binary, library\n";
+
+#[test]
+fn builtin_template() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--input",
+        "src/single_line.rs",
+        "--template",
+        "builtin:binary",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED);
+}
+
+const EXPECTED_LIST: &str = "Builtin templates (use with --template builtin:NAME):
+
+  default
+    badges, title and a license line, like the output without a template
+  minimal
+    the title only
+  dual-license
+    the usual MIT OR Apache-2.0 license and contribution sections
+  binary
+    an installation section using `cargo install`
+  library
+    a usage section with the `[dependencies]` line for the current version
+";
+
+#[test]
+fn list_builtin_templates() {
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(["readme", "--list-templates"])
+        .assert()
+        .success()
+        .stdout(EXPECTED_LIST);
+}
+
+#[test]
+fn print_builtin_template() {
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(["readme", "--print-template", "builtin:minimal"])
+        .assert()
+        .success()
+        .stdout(EXPECTED_PRINT);
+}
+
+#[test]
+fn unknown_builtin_template_fails() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/test-project",
+        "--template",
+        "builtin:miniml",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .failure()
+        .stderr(EXPECTED_UNKNOWN);
+}
@@ -0,0 +1,34 @@
+use assert_cmd::Command;
+
+const EXPECTED_WARNING: &str =
+    "Warn: `{{changelog}}` was found in template but there is no `CHANGELOG.md`\n";
+
+#[test]
+fn missing_changelog_warn() {
+    let args = ["readme", "--project-root", "tests/changelog-missing"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout("Test crate for cargo-readme\n\n\n")
+        .stderr(EXPECTED_WARNING);
+}
+
+#[test]
+fn missing_changelog_strict_should_fail() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/changelog-missing",
+        "--strict",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .failure()
+        .stderr("Error: `{{changelog}}` was found in template but there is no `CHANGELOG.md`\n");
+}
@@ -0,0 +1,39 @@
+use assert_cmd::Command;
+
+const EXPECTED_WARNING: &str = "Warn: `{{changelog}}` was found in template but `CHANGELOG.md` \
+                                has no released entries\n";
+
+#[test]
+fn changelog_entries_without_release_warn() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/changelog-unreleased",
+        "--changelog-entries",
+        "2",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout("Test crate for cargo-readme\n\n\n")
+        .stderr(EXPECTED_WARNING);
+}
+
+#[test]
+fn changelog_without_version_entry_warn() {
+    let args = ["readme", "--project-root", "tests/changelog-unreleased"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout("Test crate for cargo-readme\n\n\n")
+        .stderr(
+            "Warn: `{{changelog}}` was found in template but `CHANGELOG.md` has no entry for the \
+             documented version\n",
+        );
+}
@@ -0,0 +1,64 @@
+use assert_cmd::Command;
+
+const EXPECTED: &str = r#"# changelog
+
+Test crate for cargo-readme
+
+## What's new in 1.1.0
+
+### Fixed
+- A bug ([#12])
+
+[#12]: https://example.com/issues/12
+"#;
+
+const EXPECTED_ENTRIES: &str = r#"# changelog
+
+Test crate for cargo-readme
+
+## What's new in 1.1.0
+
+### [1.1.0] - 2024-05-01
+
+#### Fixed
+- A bug ([#12])
+
+### [1.0.0] - 2024-01-01
+
+#### Added
+- Everything
+
+[1.1.0]: https://example.com/compare/v1.0.0...v1.1.0
+[1.0.0]: https://example.com/releases/v1.0.0
+[#12]: https://example.com/issues/12
+"#;
+
+#[test]
+fn changelog_entry_for_version() {
+    let args = ["readme", "--project-root", "tests/changelog"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED);
+}
+
+#[test]
+fn changelog_latest_entries() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/changelog",
+        "--changelog-entries",
+        "2",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED_ENTRIES);
+}
@@ -0,0 +1,7 @@
+# {{crate}}
+
+{{readme}}
+
+## What's new in {{version}}
+
+{{changelog}}
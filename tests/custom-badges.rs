@@ -0,0 +1,52 @@
+use assert_cmd::Command;
+
+const EXPECTED: &str = r#"[![Crates.io](https://img.shields.io/crates/v/custom-badges.svg)](https://crates.io/crates/custom-badges)
+[![dependency status](https://deps.rs/crate/custom-badges/0.4.0/status.svg)](https://deps.rs/crate/custom-badges/0.4.0)
+[![OpenSSF Scorecard](https://api.scorecard.dev/projects/github.com/cargo-readme/test/badge "Security practices of custom-badges")](https://github.com/cargo-readme/test/security)
+![internal CI](https://ci.example.com/custom-badges.svg)
+
+# custom-badges
+
+Test crate for cargo-readme
+
+License: MIT
+"#;
+
+#[test]
+fn custom_badges_after_builtin_ones() {
+    let args = ["readme", "--project-root", "tests/custom-badges"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED);
+}
+
+const EXPECTED_GROUPS: &str = r#"[![Crates.io](https://img.shields.io/crates/v/custom-badges.svg)](https://crates.io/crates/custom-badges)
+[![dependency status](https://deps.rs/crate/custom-badges/0.4.0/status.svg)](https://deps.rs/crate/custom-badges/0.4.0)
+[![OpenSSF Scorecard](https://api.scorecard.dev/projects/github.com/cargo-readme/test/badge "Security practices of custom-badges")](https://github.com/cargo-readme/test/security)
+![internal CI](https://ci.example.com/custom-badges.svg)
+
+Test crate for cargo-readme
+"#;
+
+#[test]
+fn custom_badge_group() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/custom-badges",
+        "--template",
+        "GROUPS.tpl",
+        "--strict",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED_GROUPS);
+}
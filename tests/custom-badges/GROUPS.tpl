@@ -0,0 +1,4 @@
+{{badges.package}}
+{{badges.custom}}
+
+{{readme}}
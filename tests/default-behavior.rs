@@ -1,4 +1,6 @@
 use assert_cmd::Command;
+use std::fs::File;
+use std::path::Path;
 
 const EXPECTED: &str = r#"[![Build Status](https://travis-ci.org/livioribeiro/cargo-readme.svg?branch=master)](https://travis-ci.org/livioribeiro/cargo-readme)
 
@@ -60,3 +62,23 @@ fn default_behavior() {
         .success()
         .stdout(EXPECTED);
 }
+
+#[test]
+fn default_behavior_from_library() {
+    let project_root = Path::new("tests/test-project");
+    let mut source = File::open(project_root.join("src/lib.rs")).unwrap();
+    let mut template = File::open(project_root.join("README.tpl")).unwrap();
+
+    let readme = cargo_readme::generate_readme(
+        project_root,
+        &mut source,
+        Some(&mut template),
+        true,
+        true,
+        true,
+        true,
+        true,
+    )
+    .unwrap();
+    assert_eq!(EXPECTED, format!("{readme}\n"));
+}
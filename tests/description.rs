@@ -0,0 +1,129 @@
+use assert_cmd::Command;
+use std::fs;
+use std::path::Path;
+
+const EXPECTED: &str = r#"# description
+
+> Test crate for `cargo-readme`.
+
+Test crate for `cargo-readme`.
+
+More details here.
+"#;
+
+const EXPECTED_DRIFT: &str = "Error: `description` in Cargo.toml does not match the \
+     documentation summary\n  description: Test crate for cargo-readme\n  \
+     summary:     A crate that does something else.\n";
+
+const EXPECTED_SYNCED: &str = r#"[package]
+name = "description"
+version = "0.1.0"
+# kept short for crates.io
+description = "A crate that does something else."
+"#;
+
+#[test]
+fn summary_variable() {
+    let args = ["readme", "--project-root", "tests/description"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED);
+}
+
+#[test]
+fn check_description_matches() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/description",
+        "--check-description",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout("`description` in Cargo.toml matches the documentation summary\n");
+}
+
+#[test]
+fn check_description_reports_drift() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/description",
+        "--input",
+        "src/drift.rs",
+        "--check-description",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .failure()
+        .stderr(EXPECTED_DRIFT);
+}
+
+#[test]
+fn check_description_first_sentence() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/description",
+        "--input",
+        "src/sentences.rs",
+        "--check-description",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .failure();
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .arg("--first-sentence")
+        .assert()
+        .success()
+        .stdout("`description` in Cargo.toml matches the documentation summary\n");
+}
+
+#[test]
+fn sync_description_rewrites_manifest() {
+    // work on a copy so the fixture stays untouched
+    let root = Path::new(env!("CARGO_TARGET_TMPDIR")).join("description-sync");
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("src")).unwrap();
+    for file in ["Cargo.toml", "README.tpl", "src/lib.rs", "src/drift.rs"] {
+        fs::copy(Path::new("tests/description").join(file), root.join(file)).unwrap();
+    }
+
+    let args = [
+        "readme",
+        "--project-root",
+        root.to_str().unwrap(),
+        "--input",
+        "src/drift.rs",
+        "--sync-description",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout("Updated `description` in Cargo.toml to the documentation summary\n");
+
+    assert_eq!(
+        EXPECTED_SYNCED,
+        fs::read_to_string(root.join("Cargo.toml")).unwrap()
+    );
+}
@@ -0,0 +1,5 @@
+# {{crate}}
+
+> {{summary}}
+
+{{readme}}
@@ -0,0 +1 @@
+//! A crate that does something else.
@@ -0,0 +1,3 @@
+//! Test crate for `cargo-readme`.
+//!
+//! More details here.
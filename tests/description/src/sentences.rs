@@ -0,0 +1,3 @@
+//! Test crate for `cargo-readme`. It has more to say than the description.
+//!
+//! More details here.
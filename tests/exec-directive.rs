@@ -0,0 +1,84 @@
+use assert_cmd::Command;
+
+const EXPECTED: &str = r#"# exec-directive
+
+Test crate for cargo-readme
+
+## Usage
+
+```
+Usage: exec-directive [OPTIONS]
+```
+"#;
+
+const EXPECTED_NOT_ALLOWED: &str = "Error: The template runs \
+     `echo 'Usage: exec-directive [OPTIONS]'`; pass `--allow-exec` to allow templates to run \
+     commands\n";
+
+#[test]
+fn exec_directive() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/exec-directive",
+        "--allow-exec",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED);
+}
+
+#[test]
+fn exec_directive_requires_allow_exec() {
+    let args = ["readme", "--project-root", "tests/exec-directive"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .failure()
+        .stderr(EXPECTED_NOT_ALLOWED);
+}
+
+#[test]
+fn exec_directive_failing_command() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/exec-directive",
+        "--template",
+        "FAIL.tpl",
+        "--allow-exec",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .failure()
+        .stderr("Error: Command `false` failed with exit status: 1\n");
+}
+
+#[test]
+fn exec_output_placeholders_are_kept() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/exec-directive",
+        "--template",
+        "PLACEHOLDERS.tpl",
+        "--allow-exec",
+        "--strict",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout("Test crate for cargo-readme\n\n```\n{{version}}-{{x}}\n```\n");
+}
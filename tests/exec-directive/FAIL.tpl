@@ -0,0 +1,3 @@
+{{readme}}
+
+{{exec "false"}}
@@ -0,0 +1,7 @@
+# {{crate}}
+
+{{readme}}
+
+## Usage
+
+{{exec "echo 'Usage: exec-directive [OPTIONS]'"}}
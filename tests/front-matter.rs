@@ -0,0 +1,73 @@
+use assert_cmd::Command;
+
+const EXPECTED: &str = r#"# front-matter
+
+For contributors.
+
+Test crate for cargo-readme
+
+# Usage
+
+Headings keep their level.
+"#;
+
+const EXPECTED_STRICT: &str = "Error: Unknown template variable `{{licence}}` at line 5, column \
+     10 (did you mean `{{license}}`?)\n";
+
+const EXPECTED_BADGES: &str = r#"![Maintenance](https://img.shields.io/badge/maintenance-experimental-blue.svg?style=flat-square&color=orange)
+
+Test crate for cargo-readme
+
+# Usage
+
+Headings keep their level.
+"#;
+
+#[test]
+fn front_matter_settings() {
+    let args = ["readme", "--project-root", "tests/front-matter"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED);
+}
+
+#[test]
+fn front_matter_strict() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/front-matter",
+        "--template",
+        "STRICT.tpl",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .failure()
+        .stderr(EXPECTED_STRICT);
+}
+
+#[test]
+fn front_matter_badge_style() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/front-matter",
+        "--template",
+        "BADGES.tpl",
+        "--no-indent-headings",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED_BADGES);
+}
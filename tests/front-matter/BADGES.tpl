@@ -0,0 +1,8 @@
++++
+[badge-style]
+style = "flat-square"
++++
+
+{{badges}}
+
+{{readme}}
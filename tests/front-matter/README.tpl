@@ -0,0 +1,12 @@
+---
+no-indent-headings: true
+strict: true
+vars:
+  audience: contributors
+---
+
+# {{crate}}
+
+For {{audience}}.
+
+{{readme}}
@@ -0,0 +1,9 @@
++++
+strict = true
++++
+
+# {{crate}}
+
+{{readme}}
+
+License: {{licence}}
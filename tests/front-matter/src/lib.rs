@@ -0,0 +1,5 @@
+//! Test crate for cargo-readme
+//!
+//! # Usage
+//!
+//! Headings keep their level.
@@ -0,0 +1,23 @@
+use assert_cmd::Command;
+
+const EXPECTED: &str = r#"[![Workflow Status](https://github.com/cargo-readme/test/actions/workflows/ci.yml/badge.svg?branch=main&event=push)](https://github.com/cargo-readme/test/actions/workflows/ci.yml?query=branch%3Amain)
+[![Workflow Status](https://github.com/cargo-readme/test/actions/workflows/release.yml/badge.svg?branch=main&event=push)](https://github.com/cargo-readme/test/actions/workflows/release.yml?query=branch%3Amain)
+
+# github-workflows
+
+Test crate for cargo-readme
+
+License: MIT
+"#;
+
+#[test]
+fn badge_per_workflow_file() {
+    let args = ["readme", "--project-root", "tests/github-workflows"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED);
+}
@@ -0,0 +1,91 @@
+use assert_cmd::Command;
+use std::fs;
+use std::path::Path;
+
+const EXPECTED_ZH: &str = r#"# i18n
+
+cargo-readme 的测试 crate
+
+## 用法
+
+调用该函数。
+
+## Examples
+
+```rust
+i18n::run();
+```
+
+许可证：MIT
+"#;
+
+const EXPECTED_JA: &str = r#"# i18n
+
+Test crate for cargo-readme
+
+## Usage
+
+Call the function.
+
+## Examples
+
+```rust
+i18n::run();
+```
+
+ライセンス：MIT
+"#;
+
+#[test]
+fn locale_with_translation() {
+    let args = ["readme", "--project-root", "tests/i18n", "--locale", "zh"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED_ZH);
+}
+
+#[test]
+fn locale_without_translation() {
+    let args = ["readme", "--project-root", "tests/i18n", "--locale", "ja"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED_JA)
+        .stderr("Warn: no translation found for locale `ja` in docs/i18n/ja.md\n");
+}
+
+#[test]
+fn locale_output_file() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("i18n");
+    fs::create_dir_all(&dir).unwrap();
+    let _ = fs::remove_file(dir.join("README.zh.md"));
+    let output = dir.join("README.md");
+
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/i18n",
+        "--locale",
+        "zh",
+        "--output",
+        output.to_str().unwrap(),
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success();
+
+    assert_eq!(
+        EXPECTED_ZH,
+        fs::read_to_string(dir.join("README.zh.md")).unwrap()
+    );
+}
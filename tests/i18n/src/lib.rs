@@ -0,0 +1,13 @@
+//! Test crate for cargo-readme
+//!
+//! # Usage
+//!
+//! Call the function.
+//!
+//! # Examples
+//!
+//! ```
+//! i18n::run();
+//! ```
+
+pub fn run() {}
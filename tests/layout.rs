@@ -0,0 +1,77 @@
+use assert_cmd::Command;
+
+const EXPECTED: &str = r#"[![Crates.io](https://img.shields.io/crates/v/layout.svg)](https://crates.io/crates/layout)
+
+# The Layout Tool
+
+A tool with a well-formed README.
+
+[Documentation](https://docs.rs/layout)
+
+Test crate for cargo-readme
+
+## Installation
+
+```sh
+cargo install layout
+```
+
+License: MIT
+"#;
+
+const EXPECTED_CENTERED: &str = r#"<div align="center">
+
+# layout
+
+A tool with a well-formed README.
+
+[![Crates.io](https://img.shields.io/crates/v/layout.svg)](https://crates.io/crates/layout)
+
+[Documentation](https://docs.rs/layout)
+
+</div>
+
+Test crate for cargo-readme
+
+License: MIT
+"#;
+
+#[test]
+fn layout_options() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/layout",
+        "--title",
+        "The Layout Tool",
+        "--subtitle",
+        "--docs-link",
+        "--install-section",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED);
+}
+
+#[test]
+fn layout_centered_header() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/layout",
+        "--centered-header",
+        "--subtitle",
+        "--docs-link",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED_CENTERED);
+}
@@ -0,0 +1,3 @@
+//! Test crate for cargo-readme
+
+fn main() {}
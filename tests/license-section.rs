@@ -0,0 +1,45 @@
+use assert_cmd::Command;
+
+const EXPECTED: &str = r#"# license-section
+
+Test crate for cargo-readme
+
+## License
+
+Licensed under either of
+
+* MIT license ([LICENSE-MIT](LICENSE-MIT) or https://spdx.org/licenses/MIT.html)
+* [Apache License, Version 2.0](https://spdx.org/licenses/Apache-2.0.html)
+
+at your option.
+"#;
+
+#[test]
+fn license_section_in_template() {
+    let args = ["readme", "--project-root", "tests/license-section"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED);
+}
+
+#[test]
+fn license_section_without_template() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/license-section",
+        "--no-template",
+        "--license-section",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED);
+}
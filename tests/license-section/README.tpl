@@ -0,0 +1,7 @@
+# {{crate}}
+
+{{readme}}
+
+## License
+
+{{license_section}}
@@ -0,0 +1,52 @@
+use assert_cmd::Command;
+
+const EXPECTED: &str = r#"![Maintenance](https://img.shields.io/badge/maintenance-experimental-blue.svg)
+
+Test crate for cargo-readme
+"#;
+
+const EXPECTED_NO_BADGES: &str = "Test crate for cargo-readme\n";
+
+const EXPECTED_INVALID: &str = "Error: Invalid `[package.metadata.readme]` in Cargo.toml: \
+     invalid type: string \"yes\", expected a boolean in `no-title`\n";
+
+#[test]
+fn package_metadata_overrides_workspace_metadata() {
+    let args = ["readme", "--project-root", "tests/readme-metadata/member"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED);
+}
+
+#[test]
+fn command_line_overrides_metadata() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/readme-metadata/member",
+        "--no-badges",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED_NO_BADGES);
+}
+
+#[test]
+fn invalid_metadata_fails() {
+    let args = ["readme", "--project-root", "tests/readme-metadata-invalid"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .failure()
+        .stderr(EXPECTED_INVALID);
+}
@@ -0,0 +1,25 @@
+use assert_cmd::Command;
+
+const EXPECTED: &str = r#"[![Build Status](https://gitlab.example.com/group/project/badges/develop/pipeline.svg)](https://gitlab.example.com/group/project/commits/develop)
+[![Workflow Status](https://github.example.com/org/repo/actions/workflows/ci.yml/badge.svg)](https://github.example.com/org/repo/actions/workflows/ci.yml)
+[![Workflow Status](https://git.example.com/team/project/actions/workflows/test.yaml/badge.svg)](https://git.example.com/team/project/actions?workflow=test.yaml)
+[![Workflow Status](https://codeberg.org/owner/repo/actions/workflows/ci.yml/badge.svg?branch=main)](https://codeberg.org/owner/repo/actions?workflow=ci.yml)
+[![Build Status](https://ci.example.com/api/badges/42/status.svg?branch=main)](https://ci.example.com/repos/42)
+[![Coverage Status](https://codecov.example.com/gle/group/project/branch/master/graph/badge.svg)](https://codecov.example.com/gle/group/project)
+
+# self-hosted-forges
+
+Test crate for cargo-readme
+"#;
+
+#[test]
+fn badges_for_self_hosted_forges() {
+    let args = ["readme", "--project-root", "tests/self-hosted-forges"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED);
+}
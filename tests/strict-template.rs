@@ -0,0 +1,56 @@
+use assert_cmd::Command;
+
+const EXPECTED_NON_STRICT: &str =
+    "# strict-template\n\nTest crate for cargo-readme\n\nLicense: {{licence}}\n";
+
+const EXPECTED_ERROR: &str = "Error: Unknown template variable `{{licence}}` at line 5, column 10 \
+     (did you mean `{{license}}`?)\n";
+
+const EXPECTED_MISSING_ERROR: &str =
+    "Error: `{{badges}}` was found in template but no badges were provided\n";
+
+#[test]
+fn unknown_variable_is_kept_without_strict() {
+    let args = ["readme", "--project-root", "tests/strict-template"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED_NON_STRICT);
+}
+
+#[test]
+fn unknown_variable_fails_with_strict() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/strict-template",
+        "--strict",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .failure()
+        .stderr(EXPECTED_ERROR);
+}
+
+#[test]
+fn missing_badges_fail_with_strict() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/missing-badges-license",
+        "--strict",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .failure()
+        .stderr(EXPECTED_MISSING_ERROR);
+}
@@ -0,0 +1,5 @@
+# {{crate}}
+
+{{readme}}
+
+License: {{licence}}
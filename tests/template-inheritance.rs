@@ -0,0 +1,71 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+const EXPECTED_MEMBER: &str = r#"# member
+
+Test crate for cargo-readme
+
+Member specific notes.
+
+Part of the example workspace.
+"#;
+
+const EXPECTED_WORKSPACE_TEMPLATE: &str = r#"# plain
+
+Test crate for cargo-readme
+
+Part of the example workspace.
+"#;
+
+#[test]
+fn template_extends_workspace_template() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/template-inheritance/member",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED_MEMBER);
+}
+
+#[test]
+fn member_without_template_uses_workspace_template() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/template-inheritance/plain",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED_WORKSPACE_TEMPLATE);
+}
+
+#[test]
+fn override_of_unknown_block_should_fail() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/template-inheritance/member",
+        "--template",
+        "TYPO.tpl",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Error: Block `bdy` is not defined in template",
+        ))
+        .stderr(predicate::str::contains("(did you mean `body`?)"));
+}
@@ -0,0 +1,9 @@
+# {{crate}}
+
+{{#block body}}
+{{readme}}
+{{/block}}
+
+{{#block footer}}
+Part of the example workspace.
+{{/block}}
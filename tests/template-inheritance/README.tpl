@@ -0,0 +1 @@
+{{extends "README.member.tpl"}}
@@ -0,0 +1,7 @@
+{{extends "../README.member.tpl"}}
+
+{{#block body}}
+{{readme}}
+
+Member specific notes.
+{{/block}}
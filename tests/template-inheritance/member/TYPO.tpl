@@ -0,0 +1,5 @@
+{{extends "../README.member.tpl"}}
+
+{{#block bdy}}
+{{readme}}
+{{/block}}
@@ -0,0 +1,78 @@
+use assert_cmd::Command;
+
+const EXPECTED_MANIFEST: &str = "# template-variables\n\nTest crate for cargo-readme\n\n\
+     Requires Rust 1.80 or newer. Ask questions on the issue tracker.\n";
+
+const EXPECTED_VAR_FILE: &str = "# template-variables\n\nTest crate for cargo-readme\n\n\
+     Requires Rust 1.85 or newer. Ask questions on Zulip.\n";
+
+const EXPECTED_SET: &str = "# template-variables\n\nTest crate for cargo-readme\n\n\
+     Requires Rust 1.85 or newer. Ask questions on Discord.\n";
+
+#[test]
+fn variables_from_manifest() {
+    let args = ["readme", "--project-root", "tests/template-variables"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED_MANIFEST);
+}
+
+#[test]
+fn var_file_overrides_manifest() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/template-variables",
+        "--var-file",
+        "vars.toml",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED_VAR_FILE);
+}
+
+#[test]
+fn set_overrides_var_file() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/template-variables",
+        "--var-file",
+        "vars.toml",
+        "--set",
+        "support_channel=Discord",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED_SET);
+}
+
+#[test]
+fn set_builtin_variable_fails() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/template-variables",
+        "--set",
+        "version=2.0.0",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .failure()
+        .stderr("Error: `version` is a built-in template variable and cannot be set\n");
+}
@@ -0,0 +1,5 @@
+# {{crate}}
+
+{{readme}}
+
+{{msrv_note}} Ask questions on {{support_channel}}.
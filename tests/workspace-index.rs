@@ -0,0 +1,66 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+const EXPECTED: &str = r#"# Example workspace
+
+| Crate | Version | Description |
+|---|---|---|
+| [alpha](crates/alpha) | 0.3.1 | Parse the input. |
+| [beta](crates/beta) | 2.0.0 | Command line interface for **alpha**. |
+
+License: MIT
+"#;
+
+const EXPECTED_BADGES: &str = r#"- alpha 0.3.1: The alpha crate [![Crates.io](https://img.shields.io/crates/v/alpha.svg)](https://crates.io/crates/alpha)
+- beta 2.0.0: The alpha command line ![Maintenance](https://img.shields.io/badge/maintenance-experimental-blue.svg)
+"#;
+
+#[test]
+fn virtual_workspace_index() {
+    let args = ["readme", "--project-root", "tests/workspace-index"];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED);
+}
+
+#[test]
+fn workspace_index_with_badges_and_descriptions() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/workspace-index",
+        "--template",
+        "BADGES.tpl",
+        "--strict",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .success()
+        .stdout(EXPECTED_BADGES);
+}
+
+#[test]
+fn virtual_workspace_without_template_should_fail() {
+    let args = [
+        "readme",
+        "--project-root",
+        "tests/workspace-index",
+        "--no-template",
+    ];
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .args(args)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "workspace-index` is a virtual workspace without docs",
+        ));
+}
@@ -0,0 +1,3 @@
+{{#each members}}
+- {{name}} {{version}}: {{description}} {{badges}}
+{{/each}}
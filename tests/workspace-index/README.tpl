@@ -0,0 +1,9 @@
+# Example workspace
+
+| Crate | Version | Description |
+|---|---|---|
+{{#each members}}
+| [{{name}}]({{path}}) | {{version}} | {{summary}} |
+{{/each}}
+
+License: {{license}}
@@ -0,0 +1,3 @@
+//! Parse the input.
+//!
+//! More about alpha.